@@ -1,10 +1,12 @@
-use crate::span::{Span, Spanned};
+use crate::span::{join, join_all, EqIgnoreSpan, Span, Spanned};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub(crate) struct Block<T> {
     pub(crate) ts: Vec<T>,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub(crate) enum Statement {
     Import(Spanned<String>, Option<Spanned<String>>),
@@ -15,25 +17,169 @@ pub(crate) enum Statement {
         Spanned<Type>,
         Block<Statement>,
     ),
+    For(Spanned<String>, Expression, Block<Statement>),
+    Return(Option<Expression>),
+    Declaration {
+        mutable: bool,
+        constant: bool,
+        variable: Variable,
+    },
     Expression(Expression),
 }
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub(crate) enum Expression {
-    Identifier(Spanned<String>),
+    /// The resolved `depth` (number of enclosing scopes to walk to find the
+    /// binding) starts `None` out of the parser and is filled in by
+    /// `Resolver::resolve`. Live `velocity` models assignment as
+    /// `Expression::Binary(_, BinaryOperator::Assign, _)` rather than a separate
+    /// `Expression::Assignment`, so resolving every `Identifier` — including an
+    /// assignment's left-hand side — covers both.
+    Identifier(Spanned<String>, Option<usize>),
     Call(Spanned<Box<Expression>>, Vec<Spanned<Expression>>),
     Access(Spanned<Box<Expression>>, Spanned<Box<Expression>>),
+    Range {
+        start: Box<Expression>,
+        end: Box<Expression>,
+        inclusive: bool,
+    },
+    Lambda {
+        parameters: Vec<Variable>,
+        return_type: Spanned<Type>,
+        body: Block<Statement>,
+        span: Span,
+    },
+    Binary(Box<Expression>, BinaryOperator, Box<Expression>),
+    Unary(UnaryOperator, Box<Expression>, Span),
+    Literal(Literal, Span),
+    /// `if cond: <then> else: <else>` — a value-producing expression, not a
+    /// statement, so `var x = if c: a else: b` is legal; its value is whichever
+    /// branch's last statement ran. `else` is optional.
+    If {
+        condition: Box<Expression>,
+        then_branch: Block<Statement>,
+        else_branch: Option<Block<Statement>>,
+        span: Span,
+    },
+    While {
+        condition: Box<Expression>,
+        body: Block<Statement>,
+        span: Span,
+    },
+    Loop {
+        body: Block<Statement>,
+        span: Span,
+    },
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub(crate) enum Literal {
+    Boolean(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Char(char),
 }
 
 impl Expression {
     pub(crate) fn span(&self) -> Span {
         match self {
-            Expression::Identifier(id) => id.1.clone(),
-            Expression::Call(callee, _) => callee.1.clone(),
+            Expression::Identifier(id, _) => id.1.clone(),
+            Expression::Call(callee, args) => {
+                join_all(std::iter::once(callee.1.clone()).chain(args.iter().map(|arg| arg.1.clone())))
+            }
             Expression::Access(expr, _) => expr.1.clone(),
+            Expression::Range { start, .. } => start.span(),
+            Expression::Lambda { span, .. } => span.clone(),
+            Expression::Binary(lhs, _, rhs) => join(&lhs.span(), &rhs.span()),
+            Expression::Unary(_, _, span) => span.clone(),
+            Expression::Literal(_, span) => span.clone(),
+            Expression::If { span, .. } => span.clone(),
+            Expression::While { span, .. } => span.clone(),
+            Expression::Loop { span, .. } => span.clone(),
+        }
+    }
+}
+
+/// Binary operators, grouped into the precedence tiers the parser's table-driven
+/// climbing loop consults: multiplicative > additive > shift > comparison > equality
+/// > bitwise-and > bitwise-xor > bitwise-or > logical-and > logical-or > assignment.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum BinaryOperator {
+    Plus,
+    Minus,
+    Asterisk,
+    Slash,
+    Percent,
+    ShiftLeft,
+    ShiftRight,
+    LessThan,
+    LessThanEquals,
+    GreaterThan,
+    GreaterThanEquals,
+    EqualsEquals,
+    BangEquals,
+    BitwiseAnd,
+    BitwiseXor,
+    BitwiseOr,
+    And,
+    Or,
+    Assign,
+    PlusEquals,
+    MinusEquals,
+    AsteriskEquals,
+    SlashEquals,
+    PercentEquals,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Associativity {
+    Left,
+    Right,
+}
+
+impl BinaryOperator {
+    pub(crate) fn precedence(&self) -> u8 {
+        use BinaryOperator::*;
+        match self {
+            Assign | PlusEquals | MinusEquals | AsteriskEquals | SlashEquals | PercentEquals => 1,
+            Or => 2,
+            And => 3,
+            BitwiseOr => 4,
+            BitwiseXor => 5,
+            BitwiseAnd => 6,
+            EqualsEquals | BangEquals => 7,
+            LessThan | LessThanEquals | GreaterThan | GreaterThanEquals => 8,
+            ShiftLeft | ShiftRight => 9,
+            Plus | Minus => 10,
+            Asterisk | Slash | Percent => 11,
+        }
+    }
+
+    pub(crate) fn associativity(&self) -> Associativity {
+        match self {
+            BinaryOperator::Assign
+            | BinaryOperator::PlusEquals
+            | BinaryOperator::MinusEquals
+            | BinaryOperator::AsteriskEquals
+            | BinaryOperator::SlashEquals
+            | BinaryOperator::PercentEquals => Associativity::Right,
+            _ => Associativity::Left,
         }
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum UnaryOperator {
+    Minus,
+    Bang,
+    BitwiseNot,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub(crate) enum Type {
     Unit,
@@ -43,11 +189,192 @@ pub(crate) enum Type {
     MutableReference(Box<Type>),
     Id(String),
     Polymorphic(String, Vec<Spanned<Type>>),
+    Function(Vec<Type>, Box<Type>),
+    /// No `: Type` annotation was written; the type should be inferred from the
+    /// declaration's initializer.
+    Inferred,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub(crate) struct Variable {
     pub(crate) name: Spanned<String>,
     pub(crate) ty: Spanned<Type>,
     pub(crate) initializer: Option<Spanned<Expression>>,
 }
+
+impl<T: EqIgnoreSpan> EqIgnoreSpan for Block<T> {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        self.ts.eq_ignore_span(&other.ts)
+    }
+}
+
+impl EqIgnoreSpan for Variable {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        self.name.eq_ignore_span(&other.name)
+            && self.ty.eq_ignore_span(&other.ty)
+            && self.initializer.eq_ignore_span(&other.initializer)
+    }
+}
+
+impl EqIgnoreSpan for Statement {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Statement::Import(name_a, alias_a), Statement::Import(name_b, alias_b)) => {
+                name_a.eq_ignore_span(name_b) && alias_a.eq_ignore_span(alias_b)
+            }
+            (Statement::Struct(name_a, fields_a), Statement::Struct(name_b, fields_b)) => {
+                name_a.eq_ignore_span(name_b) && fields_a.eq_ignore_span(fields_b)
+            }
+            (
+                Statement::Function(name_a, params_a, ret_a, body_a),
+                Statement::Function(name_b, params_b, ret_b, body_b),
+            ) => {
+                name_a.eq_ignore_span(name_b)
+                    && params_a.eq_ignore_span(params_b)
+                    && ret_a.eq_ignore_span(ret_b)
+                    && body_a.eq_ignore_span(body_b)
+            }
+            (
+                Statement::For(name_a, iterable_a, body_a),
+                Statement::For(name_b, iterable_b, body_b),
+            ) => {
+                name_a.eq_ignore_span(name_b)
+                    && iterable_a.eq_ignore_span(iterable_b)
+                    && body_a.eq_ignore_span(body_b)
+            }
+            (Statement::Return(a), Statement::Return(b)) => a.eq_ignore_span(b),
+            (
+                Statement::Declaration {
+                    mutable: mutable_a,
+                    constant: constant_a,
+                    variable: variable_a,
+                },
+                Statement::Declaration {
+                    mutable: mutable_b,
+                    constant: constant_b,
+                    variable: variable_b,
+                },
+            ) => mutable_a == mutable_b && constant_a == constant_b && variable_a.eq_ignore_span(variable_b),
+            (Statement::Expression(a), Statement::Expression(b)) => a.eq_ignore_span(b),
+            _ => false,
+        }
+    }
+}
+
+impl EqIgnoreSpan for Literal {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Literal::Boolean(a), Literal::Boolean(b)) => a == b,
+            (Literal::Int(a), Literal::Int(b)) => a == b,
+            (Literal::Float(a), Literal::Float(b)) => a == b,
+            (Literal::Str(a), Literal::Str(b)) => a == b,
+            (Literal::Char(a), Literal::Char(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl EqIgnoreSpan for Expression {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Expression::Identifier(a, _), Expression::Identifier(b, _)) => a.eq_ignore_span(b),
+            (Expression::Call(callee_a, args_a), Expression::Call(callee_b, args_b)) => {
+                callee_a.eq_ignore_span(callee_b) && args_a.eq_ignore_span(args_b)
+            }
+            (Expression::Access(a, b), Expression::Access(c, d)) => {
+                a.eq_ignore_span(c) && b.eq_ignore_span(d)
+            }
+            (
+                Expression::Range {
+                    start: start_a,
+                    end: end_a,
+                    inclusive: inclusive_a,
+                },
+                Expression::Range {
+                    start: start_b,
+                    end: end_b,
+                    inclusive: inclusive_b,
+                },
+            ) => {
+                start_a.eq_ignore_span(start_b)
+                    && end_a.eq_ignore_span(end_b)
+                    && inclusive_a == inclusive_b
+            }
+            (
+                Expression::Lambda {
+                    parameters: parameters_a,
+                    return_type: return_type_a,
+                    body: body_a,
+                    ..
+                },
+                Expression::Lambda {
+                    parameters: parameters_b,
+                    return_type: return_type_b,
+                    body: body_b,
+                    ..
+                },
+            ) => {
+                parameters_a.eq_ignore_span(parameters_b)
+                    && return_type_a.eq_ignore_span(return_type_b)
+                    && body_a.eq_ignore_span(body_b)
+            }
+            (Expression::Binary(lhs_a, op_a, rhs_a), Expression::Binary(lhs_b, op_b, rhs_b)) => {
+                lhs_a.eq_ignore_span(lhs_b) && op_a == op_b && rhs_a.eq_ignore_span(rhs_b)
+            }
+            (Expression::Unary(op_a, operand_a, _), Expression::Unary(op_b, operand_b, _)) => {
+                op_a == op_b && operand_a.eq_ignore_span(operand_b)
+            }
+            (Expression::Literal(literal_a, _), Expression::Literal(literal_b, _)) => {
+                literal_a.eq_ignore_span(literal_b)
+            }
+            (
+                Expression::If {
+                    condition: condition_a,
+                    then_branch: then_a,
+                    else_branch: else_a,
+                    ..
+                },
+                Expression::If {
+                    condition: condition_b,
+                    then_branch: then_b,
+                    else_branch: else_b,
+                    ..
+                },
+            ) => {
+                condition_a.eq_ignore_span(condition_b)
+                    && then_a.eq_ignore_span(then_b)
+                    && else_a.eq_ignore_span(else_b)
+            }
+            (
+                Expression::While { condition: condition_a, body: body_a, .. },
+                Expression::While { condition: condition_b, body: body_b, .. },
+            ) => condition_a.eq_ignore_span(condition_b) && body_a.eq_ignore_span(body_b),
+            (Expression::Loop { body: body_a, .. }, Expression::Loop { body: body_b, .. }) => {
+                body_a.eq_ignore_span(body_b)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl EqIgnoreSpan for Type {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Type::Unit, Type::Unit) => true,
+            (Type::Int, Type::Int) => true,
+            (Type::Float, Type::Float) => true,
+            (Type::Inferred, Type::Inferred) => true,
+            (Type::Reference(a), Type::Reference(b)) => a.eq_ignore_span(b),
+            (Type::MutableReference(a), Type::MutableReference(b)) => a.eq_ignore_span(b),
+            (Type::Id(a), Type::Id(b)) => a == b,
+            (Type::Polymorphic(name_a, args_a), Type::Polymorphic(name_b, args_b)) => {
+                name_a == name_b && args_a.eq_ignore_span(args_b)
+            }
+            (Type::Function(params_a, ret_a), Type::Function(params_b, ret_b)) => {
+                params_a.eq_ignore_span(params_b) && ret_a.eq_ignore_span(ret_b)
+            }
+            _ => false,
+        }
+    }
+}
@@ -1,47 +1,119 @@
 use std::rc::Rc;
 
-use crate::{error::Error, parser::Parser, tokenizer::Tokenizer};
+use crate::{
+    error::Error,
+    parser::Parser,
+    resolver::Resolver,
+    span::{Color, SourceMap},
+    tokenizer::Tokenizer,
+};
+
+/// A compilation unit before its contents are known: either a path to read from
+/// disk, or a virtual source fed in directly (snapshot tests, a REPL, stdin).
+enum Unit {
+    File(String),
+    InMemory(String, String),
+}
 
 pub(crate) struct Compiler {
-    files: Vec<String>,
+    units: Vec<Unit>,
+    color: Color,
 }
 
 impl Compiler {
     pub(crate) fn new() -> Compiler {
-        Compiler { files: Vec::new() }
+        Compiler {
+            units: Vec::new(),
+            color: Color::Auto,
+        }
     }
 
     pub(crate) fn add_file(&mut self, filename: String) {
-        self.files.push(filename);
+        self.units.push(Unit::File(filename));
     }
 
-    pub(crate) fn compile(&self) -> Result<(), Error> {
-        for filename in &self.files {
-            let contents: String = match std::fs::read_to_string(filename.as_str()) {
-                Ok(contents) => contents,
-                Err(_) => {
-                    return Err(Error::new(
-                        format!("failed to read file '{}'", filename).as_str(),
-                        (Rc::new("<stdin>".to_string()), (0, 0)..(0, 0)),
-                    ))
-                }
+    /// Feeds a virtual source unit directly, with no filesystem access involved.
+    /// `name` is still used for diagnostics and spans, exactly as a filename would
+    /// be. Useful for snapshot tests, a REPL, or anything else driving the
+    /// compiler from memory.
+    pub(crate) fn add_source(&mut self, name: String, contents: String) {
+        self.units.push(Unit::InMemory(name, contents));
+    }
+
+    /// Reads all of stdin into a virtual unit named `name`, for `-`-style piped
+    /// input.
+    pub(crate) fn add_stdin(&mut self, name: String) -> std::io::Result<()> {
+        use std::io::Read;
+        let mut contents = String::new();
+        std::io::stdin().read_to_string(&mut contents)?;
+        self.add_source(name, contents);
+        Ok(())
+    }
+
+    /// Overrides automatic TTY/`NO_COLOR` detection for diagnostic rendering; see
+    /// `Color`.
+    pub(crate) fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    /// Compiles every added unit, continuing past a failing one instead of
+    /// stopping at the first one, so a single run reports every unit's
+    /// diagnostics at once instead of making users fix-and-recompile one at a
+    /// time.
+    pub(crate) fn compile(&self) -> std::result::Result<(), Vec<Error>> {
+        let source_map = SourceMap::shared();
+        source_map.borrow_mut().set_color(self.color);
+        let mut errors = Vec::new();
+        for unit in &self.units {
+            let (name, contents) = match unit {
+                Unit::File(filename) => match std::fs::read_to_string(filename.as_str()) {
+                    Ok(contents) => (filename.clone(), contents),
+                    Err(_) => {
+                        let file = source_map
+                            .borrow_mut()
+                            .add_file(filename.clone(), String::new());
+                        errors.push(Error::new(
+                            format!("failed to read file '{}'", filename).as_str(),
+                            (file, 0..0),
+                            source_map.clone(),
+                        ));
+                        continue;
+                    }
+                },
+                Unit::InMemory(name, contents) => (name.clone(), contents.clone()),
             };
-            let mut tokenizer = Tokenizer::new(Rc::new(filename.clone()), contents);
+            let file = source_map.borrow_mut().add_file(name, contents.clone());
+            let mut tokenizer = Tokenizer::new(file, Rc::new(contents), source_map.clone());
             let tokens = match tokenizer.tokenize() {
                 Ok(tokens) => tokens,
-                Err(err) => return Err(err),
+                Err(tokenize_errors) => {
+                    errors.extend(tokenize_errors);
+                    continue;
+                }
             };
 
-            let mut parser = Parser::new(tokens);
-            let statements = match parser.parse() {
+            let mut parser = Parser::new(tokens, source_map.clone());
+            let mut statements = match parser.parse() {
                 Ok(statements) => statements,
-                Err(err) => return Err(err),
+                Err(parse_errors) => {
+                    errors.extend(parse_errors);
+                    continue;
+                }
             };
 
+            if let Err(resolve_errors) = Resolver::resolve(&mut statements, source_map.clone()) {
+                errors.extend(resolve_errors);
+                continue;
+            }
+
             for statement in statements {
                 println!("{:?}", statement);
             }
         }
-        Ok(())
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
 }
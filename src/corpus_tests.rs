@@ -0,0 +1,173 @@
+//! File-based parser conformance tests: each fixture under `tests/corpus/` is
+//! either expected to parse cleanly (`must_parse/`) or to fail with a diagnostic
+//! (`must_fail/`). Expected trees are written with throwaway spans and compared
+//! with [`EqIgnoreSpan`], so fixtures don't need to track exact byte offsets.
+#![cfg(test)]
+
+use std::rc::Rc;
+
+use crate::{
+    ast::{Expression, Literal, Statement, Type, Variable},
+    error::Error,
+    parser::Parser,
+    span::{assert_eq_ignore_span, spanned, SourceMap},
+    tokenizer::{Tokenizer, TokenKind},
+};
+
+const DUMMY_SPAN: crate::span::Span = (0, 0..0);
+
+fn parse(source: &str) -> std::result::Result<Vec<Statement>, Vec<Error>> {
+    let source_map = SourceMap::shared();
+    let file = source_map
+        .borrow_mut()
+        .add_file("<corpus>".to_string(), source.to_string());
+    let mut tokenizer = Tokenizer::new(file, Rc::new(source.to_string()), source_map.clone());
+    let tokens = tokenizer
+        .tokenize()
+        .expect("corpus fixtures must tokenize cleanly");
+    Parser::new(tokens, source_map).parse()
+}
+
+#[test]
+fn var_declaration() {
+    let source = include_str!("../tests/corpus/must_parse/var_declaration.vel");
+    let statements = parse(source).expect("must_parse/var_declaration.vel should parse");
+    let expected = vec![Statement::Declaration {
+        mutable: false,
+        constant: false,
+        variable: Variable {
+            name: spanned("x".to_string(), DUMMY_SPAN),
+            ty: spanned(Type::Inferred, DUMMY_SPAN),
+            initializer: Some(spanned(
+                Expression::Literal(Literal::Int(1), DUMMY_SPAN),
+                DUMMY_SPAN,
+            )),
+        },
+    }];
+    assert_eq_ignore_span(&statements, &expected);
+}
+
+#[test]
+fn numeric_literal_bases() {
+    let source = include_str!("../tests/corpus/must_parse/numeric_literal_bases.vel");
+    let statements = parse(source).expect("must_parse/numeric_literal_bases.vel should parse");
+    let expected = vec![Statement::Declaration {
+        mutable: false,
+        constant: false,
+        variable: Variable {
+            name: spanned("x".to_string(), DUMMY_SPAN),
+            ty: spanned(Type::Inferred, DUMMY_SPAN),
+            initializer: Some(spanned(
+                Expression::Literal(Literal::Int(0xFF00), DUMMY_SPAN),
+                DUMMY_SPAN,
+            )),
+        },
+    }];
+    assert_eq_ignore_span(&statements, &expected);
+}
+
+/// An out-of-range integer literal should be a reported `Error`, not a panic
+/// from an `.unwrap()` on the underlying `str::parse`.
+#[test]
+fn integer_literal_overflow_fails_without_panicking() {
+    let source = include_str!("../tests/corpus/must_fail/integer_literal_overflow.vel");
+    assert!(
+        parse(source).is_err(),
+        "must_fail/integer_literal_overflow.vel should not parse"
+    );
+}
+
+#[test]
+fn if_else_expression() {
+    let source = include_str!("../tests/corpus/must_parse/if_else_expression.vel");
+    let statements = parse(source).expect("must_parse/if_else_expression.vel should parse");
+    let expected = vec![Statement::Declaration {
+        mutable: false,
+        constant: false,
+        variable: Variable {
+            name: spanned("y".to_string(), DUMMY_SPAN),
+            ty: spanned(Type::Inferred, DUMMY_SPAN),
+            initializer: Some(spanned(
+                Expression::If {
+                    condition: Box::new(Expression::Identifier(spanned("x".to_string(), DUMMY_SPAN), None)),
+                    then_branch: crate::ast::Block {
+                        ts: vec![Statement::Expression(Expression::Literal(Literal::Int(1), DUMMY_SPAN))],
+                    },
+                    else_branch: Some(crate::ast::Block {
+                        ts: vec![Statement::Expression(Expression::Literal(Literal::Int(0), DUMMY_SPAN))],
+                    }),
+                    span: DUMMY_SPAN,
+                },
+                DUMMY_SPAN,
+            )),
+        },
+    }];
+    assert_eq_ignore_span(&statements, &expected);
+}
+
+#[test]
+fn function_add() {
+    let source = include_str!("../tests/corpus/must_parse/function_add.vel");
+    let statements = parse(source).expect("must_parse/function_add.vel should parse");
+    let expected = vec![Statement::Function(
+        spanned("add".to_string(), DUMMY_SPAN),
+        vec![
+            Variable {
+                name: spanned("a".to_string(), DUMMY_SPAN),
+                ty: spanned(Type::Int, DUMMY_SPAN),
+                initializer: None,
+            },
+            Variable {
+                name: spanned("b".to_string(), DUMMY_SPAN),
+                ty: spanned(Type::Int, DUMMY_SPAN),
+                initializer: None,
+            },
+        ],
+        spanned(Type::Int, DUMMY_SPAN),
+        crate::ast::Block {
+            ts: vec![Statement::Return(Some(Expression::Binary(
+                Box::new(Expression::Identifier(spanned("a".to_string(), DUMMY_SPAN), None)),
+                crate::ast::BinaryOperator::Plus,
+                Box::new(Expression::Identifier(spanned("b".to_string(), DUMMY_SPAN), None)),
+            )))],
+        },
+    )];
+    assert_eq_ignore_span(&statements, &expected);
+}
+
+#[test]
+fn missing_closing_paren_fails() {
+    let source = include_str!("../tests/corpus/must_fail/missing_closing_paren.vel");
+    assert!(
+        parse(source).is_err(),
+        "must_fail/missing_closing_paren.vel should not parse"
+    );
+}
+
+/// An error from an alternation point (here, `primary`'s fallback) carries the
+/// set of token kinds `primary` could have started an expression with, not just
+/// a flat message, so tooling can use it without reparsing the text.
+#[test]
+fn unexpected_token_carries_expected_kinds() {
+    let source = include_str!("../tests/corpus/must_fail/unexpected_token.vel");
+    let errors = parse(source).expect_err("must_fail/unexpected_token.vel should not parse");
+    assert!(
+        errors[0].expected.contains(&TokenKind::Identifier),
+        "expected set should include every kind primary() could have accepted, got {:?}",
+        errors[0].expected
+    );
+}
+
+/// `parse`'s error arm calls `synchronize` instead of a single `advance`, so one
+/// broken declaration doesn't swallow the rest of the file: both lines here are
+/// independently malformed, and both should be reported in one pass.
+#[test]
+fn panic_mode_recovery_collects_multiple_errors() {
+    let source = include_str!("../tests/corpus/must_fail/two_broken_declarations.vel");
+    let errors = parse(source).expect_err("must_fail/two_broken_declarations.vel should not parse");
+    assert_eq!(
+        errors.len(),
+        2,
+        "synchronize should let the parser recover after the first error and report the second too"
+    );
+}
@@ -0,0 +1,165 @@
+use crate::span::{SourceMap, Span};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    fn label(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+
+    /// ANSI color code the underline is painted in when color is enabled.
+    fn color_code(&self) -> &'static str {
+        match self {
+            Severity::Error => "31",   // red
+            Severity::Warning => "33", // yellow
+            Severity::Note => "36",    // cyan
+        }
+    }
+}
+
+/// Wraps `text` in the given ANSI SGR `code` when `enabled`, otherwise returns it
+/// untouched, so every call site stays readable without an `if` of its own.
+fn paint(enabled: bool, code: &str, text: &str) -> String {
+    if enabled {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Label {
+    pub(crate) span: Span,
+    pub(crate) message: String,
+}
+
+impl Label {
+    pub(crate) fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// A single diagnostic: a severity, a headline title, one or more labeled subspans
+/// pointing at the offending source, and an optional hint rendered below them.
+#[derive(Debug, Clone)]
+pub(crate) struct DiagnosticFrame {
+    pub(crate) severity: Severity,
+    pub(crate) title: String,
+    pub(crate) labels: Vec<Label>,
+    pub(crate) hint: Option<String>,
+}
+
+impl DiagnosticFrame {
+    pub(crate) fn new(severity: Severity, title: impl Into<String>) -> Self {
+        Self {
+            severity,
+            title: title.into(),
+            labels: Vec::new(),
+            hint: None,
+        }
+    }
+
+    pub(crate) fn error(title: impl Into<String>) -> Self {
+        Self::new(Severity::Error, title)
+    }
+
+    pub(crate) fn with_label(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.labels.push(Label::new(span, message));
+        self
+    }
+
+    pub(crate) fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    /// Renders the frame against `source_map`, printing each labeled line behind a
+    /// dimmed line-number gutter with a `^^^` underline (colored by severity)
+    /// beneath the label's columns and its message alongside it. A label whose
+    /// span crosses multiple lines underlines the first line through its end and
+    /// the last line up to where the span actually ends, with a `...` gutter
+    /// standing in for any lines skipped in between. Color is resolved from
+    /// `source_map`'s `Color` mode (see `Compiler::set_color`) and follows
+    /// `NO_COLOR`/TTY detection when that mode is `Auto`.
+    pub(crate) fn render(&self, source_map: &SourceMap) -> String {
+        let color = source_map.color_enabled();
+        let mut out = String::new();
+        if let Some(primary) = self.labels.first() {
+            let filename = source_map.filename(primary.span.0);
+            let (line_number, column, _, _) = source_map.resolve(&primary.span);
+            out.push_str(&format!(
+                "{}:{}:{}: {}\n",
+                filename,
+                line_number,
+                column,
+                paint(color, "1", &format!("{}: {}", self.severity.label(), self.title)),
+            ));
+        } else {
+            out.push_str(&format!(
+                "{}\n",
+                paint(color, "1", &format!("{}: {}", self.severity.label(), self.title)),
+            ));
+        }
+        let gutter = |line: usize| paint(color, "2", &format!("{:>4} |", line));
+        let blank_gutter = paint(color, "2", "     |");
+        for label in &self.labels {
+            let file = label.span.0;
+            let (start_line, start_col, end_line, end_col) = source_map.resolve(&label.span);
+            if start_line == end_line {
+                let line = source_map.line_text(file, start_line);
+                out.push_str(&format!("{} {}\n", gutter(start_line), line));
+                out.push_str(&format!(
+                    "{} {}{} {}\n",
+                    blank_gutter,
+                    " ".repeat(start_col.saturating_sub(1)),
+                    paint(
+                        color,
+                        self.severity.color_code(),
+                        &"^".repeat(end_col.saturating_sub(start_col).max(1)),
+                    ),
+                    label.message
+                ));
+            } else {
+                let first_line = source_map.line_text(file, start_line);
+                out.push_str(&format!("{} {}\n", gutter(start_line), first_line));
+                out.push_str(&format!(
+                    "{} {}{}\n",
+                    blank_gutter,
+                    " ".repeat(start_col.saturating_sub(1)),
+                    paint(
+                        color,
+                        self.severity.color_code(),
+                        &"^".repeat((first_line.chars().count() + 1).saturating_sub(start_col).max(1)),
+                    ),
+                ));
+                if end_line > start_line + 1 {
+                    out.push_str(&format!("{}\n", paint(color, "2", " ... |")));
+                }
+                let last_line = source_map.line_text(file, end_line);
+                out.push_str(&format!("{} {}\n", gutter(end_line), last_line));
+                out.push_str(&format!(
+                    "{} {} {}\n",
+                    blank_gutter,
+                    paint(color, self.severity.color_code(), &"^".repeat(end_col.saturating_sub(1).max(1))),
+                    label.message
+                ));
+            }
+        }
+        if let Some(hint) = &self.hint {
+            out.push_str(&format!("  = hint: {}\n", hint));
+        }
+        out
+    }
+}
@@ -1,48 +1,42 @@
-use crate::span::Span;
-use colored::*;
-use std::fmt::Display;
-
-pub(crate) type Result<T> = std::result::Result<T, Error>;
-
-pub(crate) struct Error {
-    message: String,
-    span: Span,
-}
-
-impl Error {
-    pub(crate) fn new(message: impl Into<String>, span: Span) -> Self {
-        Self {
-            message: message.into(),
-            span,
-        }
-    }
-}
-
-impl Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (filename, range) = self.span.clone();
-        let (start, end) = (range.start, range.end);
-        let (line_number, (start, end)) = (start.0, (start.1, end.1));
-        let contents: String = std::fs::read_to_string(filename.as_str()).unwrap();
-        let line = contents.lines().nth(line_number - 1).unwrap();
-
-        let mut out = String::new();
-        out.push_str(&format!(
-            "{}{}{}\n",
-            format!("{}:{}:{}: ", filename, line_number, start)
-                .white()
-                .bold(),
-            "error: ".red().bold(),
-            self.message.white().bold()
-        ));
-        out.push_str(&format!("{}\n", line));
-        out.push_str(
-            format!("{}^{}", " ".repeat(start - 1), "~".repeat(end - start),)
-                .green()
-                .to_string()
-                .as_str(),
-        );
-
-        write!(f, "{}", out)
-    }
-}
+use crate::diagnostic::DiagnosticFrame;
+use crate::span::{SharedSourceMap, Span};
+use crate::tokenizer::TokenKind;
+use std::fmt::Display;
+
+pub(crate) type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub(crate) struct Error {
+    frame: DiagnosticFrame,
+    source_map: SharedSourceMap,
+    /// The token kinds that would have been accepted where this error occurred,
+    /// for machine consumers (e.g. editor completion) that want more than the
+    /// rendered message. Empty unless this came from `Error::new`/`from_frame`
+    /// through `with_expected` — most errors (lexer errors, resolver errors)
+    /// aren't a simple "expected one of these kinds" mismatch.
+    pub(crate) expected: Vec<TokenKind>,
+}
+
+impl Error {
+    pub(crate) fn new(message: impl Into<String>, span: Span, source_map: SharedSourceMap) -> Self {
+        let frame = DiagnosticFrame::error(message.into()).with_label(span, "here");
+        Self::from_frame(frame, source_map)
+    }
+
+    pub(crate) fn from_frame(frame: DiagnosticFrame, source_map: SharedSourceMap) -> Self {
+        Self { frame, source_map, expected: Vec::new() }
+    }
+
+    /// Attaches the set of token kinds that would have been accepted instead of
+    /// what was actually found, so callers don't have to reparse the message.
+    pub(crate) fn with_expected(mut self, expected: Vec<TokenKind>) -> Self {
+        self.expected = expected;
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.frame.render(&self.source_map.borrow()))
+    }
+}
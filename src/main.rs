@@ -1,29 +1,57 @@
 use compiler::Compiler;
+use span::Color;
 
 mod ast;
 mod compiler;
+#[cfg(test)]
+mod corpus_tests;
+mod diagnostic;
 mod error;
 mod parser;
+mod resolver;
+#[cfg(test)]
+mod resolver_tests;
+#[cfg(test)]
+mod serde_tests;
 mod span;
 mod tokenizer;
+#[cfg(test)]
+mod tokenizer_bench;
 
 fn main() {
     let args: Vec<String> = std::env::args().skip(1).collect::<Vec<String>>();
     if args.len() == 0 {
-        println!("Usage: velocity <filename>");
+        println!("Usage: velocity [--color=always|never|auto] <filename|->...");
         return;
     }
 
     let mut compiler = Compiler::new();
-    for filename in args {
-        compiler.add_file(filename);
+    for arg in args {
+        match arg.strip_prefix("--color=") {
+            Some("always") => compiler.set_color(Color::Always),
+            Some("never") => compiler.set_color(Color::Never),
+            Some("auto") => compiler.set_color(Color::Auto),
+            Some(other) => {
+                eprintln!("unknown --color value '{}', expected always, never, or auto", other);
+                return;
+            }
+            None if arg == "-" => {
+                if let Err(err) = compiler.add_stdin("<stdin>".to_string()) {
+                    eprintln!("failed to read stdin: {}", err);
+                    return;
+                }
+            }
+            None => compiler.add_file(arg),
+        }
     }
 
     match compiler.compile() {
         Ok(_) => {}
-        Err(err) => {
-            eprintln!("{}", err);
-            return;
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("{}", error);
+            }
+            std::process::exit(1);
         }
     }
 }
@@ -1,31 +1,89 @@
 use crate::{
-    ast::{Block, Expression, Statement, Type, Variable},
+    ast::{
+        Associativity, BinaryOperator, Block, Expression, Literal, Statement, Type, UnaryOperator,
+        Variable,
+    },
     error::{Error, Result},
-    span::spanned,
+    span::{spanned, SharedSourceMap, Span},
     tokenizer::{Token, TokenKind},
 };
 
-#[derive(Debug, Clone)]
+/// Every token kind `primary` knows how to start an expression with, in the
+/// order its `match` tries them — surfaced verbatim in an "expected one of"
+/// error when none of them match.
+const PRIMARY_EXPECTED: &[TokenKind] = &[
+    TokenKind::Identifier,
+    TokenKind::LeftParenthesis,
+    TokenKind::Fn,
+    TokenKind::If,
+    TokenKind::While,
+    TokenKind::Loop,
+    TokenKind::String,
+    TokenKind::Character,
+    TokenKind::Integer,
+    TokenKind::Floating,
+];
+
+#[derive(Clone)]
 pub(crate) struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    source_map: SharedSourceMap,
 }
 
 impl Parser {
-    pub(crate) fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+    pub(crate) fn new(tokens: Vec<Token>, source_map: SharedSourceMap) -> Self {
+        Self {
+            tokens,
+            current: 0,
+            source_map,
+        }
     }
 
-    pub(crate) fn parse(&mut self) -> Result<Vec<Statement>> {
+    pub(crate) fn parse(&mut self) -> std::result::Result<Vec<Statement>, Vec<Error>> {
         let mut statements = vec![];
+        let mut errors = vec![];
         while !self.is_at_end() {
             if self.check(TokenKind::Linefeed) {
                 self.advance();
                 continue;
             }
-            statements.push(self.statement()?);
+            match self.statement() {
+                Ok(statement) => statements.push(statement),
+                Err(err) => {
+                    errors.push(err);
+                    self.synchronize();
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(statements)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Discards tokens until the next likely statement boundary, so a single
+    /// syntax error doesn't prevent `parse` from reporting the rest.
+    fn synchronize(&mut self) {
+        while !self.is_at_end() {
+            if self.check(TokenKind::Linefeed) || self.check(TokenKind::Dedent) {
+                self.advance();
+                return;
+            }
+            match self.current().kind {
+                TokenKind::Import
+                | TokenKind::Struct
+                | TokenKind::Fn
+                | TokenKind::Var
+                | TokenKind::Const
+                | TokenKind::For
+                | TokenKind::Return => return,
+                _ => {
+                    self.advance();
+                }
+            }
         }
-        Ok(statements)
     }
 
     fn statement(&mut self) -> Result<Statement> {
@@ -33,6 +91,9 @@ impl Parser {
             TokenKind::Import => self.import(),
             TokenKind::Struct => self.struct_(),
             TokenKind::Fn => self.function(),
+            TokenKind::For => self.for_(),
+            TokenKind::Return => self.return_(),
+            TokenKind::Var | TokenKind::Const => self.declaration(),
             _ => {
                 let expr = self.expression()?;
                 Ok(Statement::Expression(expr))
@@ -40,6 +101,66 @@ impl Parser {
         }
     }
 
+    fn return_(&mut self) -> Result<Statement> {
+        self.consume(TokenKind::Return)?;
+        let value = if self.check(TokenKind::Linefeed) || self.check(TokenKind::Dedent) {
+            None
+        } else {
+            Some(self.expression()?)
+        };
+        Ok(Statement::Return(value))
+    }
+
+    fn declaration(&mut self) -> Result<Statement> {
+        let constant = self.check(TokenKind::Const);
+        if constant {
+            self.consume(TokenKind::Const)?;
+        } else {
+            self.consume(TokenKind::Var)?;
+        }
+        let mutable = self.check(TokenKind::Mut);
+        if mutable {
+            self.consume(TokenKind::Mut)?;
+        }
+        let name = self.consume(TokenKind::Identifier)?.clone();
+        let ty = if self.check(TokenKind::Colon) {
+            self.consume(TokenKind::Colon)?;
+            let span = self.current().span.clone();
+            spanned(self.type_()?, span)
+        } else {
+            spanned(Type::Inferred, name.span.clone())
+        };
+        let initializer = if self.check(TokenKind::Equals) {
+            self.consume(TokenKind::Equals)?;
+            let expr = self.expression()?;
+            Some(spanned(expr.clone(), expr.span()))
+        } else {
+            None
+        };
+        Ok(Statement::Declaration {
+            mutable,
+            constant,
+            variable: Variable {
+                name: spanned(name.lexeme.to_string().clone(), name.span.clone()),
+                ty,
+                initializer,
+            },
+        })
+    }
+
+    fn for_(&mut self) -> Result<Statement> {
+        self.consume(TokenKind::For)?;
+        let name = self.consume(TokenKind::Identifier)?.clone();
+        self.consume(TokenKind::In)?;
+        let iterable = self.expression()?;
+        let block = self.block(|parser| parser.statement())?;
+        Ok(Statement::For(
+            spanned(name.lexeme.to_string().clone(), name.span.clone()),
+            iterable,
+            block,
+        ))
+    }
+
     fn import(&mut self) -> Result<Statement> {
         self.consume(TokenKind::Import)?;
         let name = self.consume(TokenKind::Identifier)?.clone();
@@ -122,6 +243,97 @@ impl Parser {
     }
 
     fn expression(&mut self) -> Result<Expression> {
+        self.parse_binary(0)
+    }
+
+    /// Table-driven precedence-climbing loop: parses a unary operand, then keeps
+    /// folding in binary operators whose precedence is at least `min_bp`, recursing
+    /// with `precedence + 1` for left-associative operators and `precedence` for
+    /// right-associative ones (assignment).
+    fn parse_binary(&mut self, min_bp: u8) -> Result<Expression> {
+        let mut lhs = self.parse_unary()?;
+        while let Some(op) = Self::binary_operator(&self.current().kind) {
+            let bp = op.precedence();
+            if bp < min_bp {
+                break;
+            }
+            self.advance();
+            let next_min = match op.associativity() {
+                Associativity::Left => bp + 1,
+                Associativity::Right => bp,
+            };
+            let rhs = self.parse_binary(next_min)?;
+            lhs = Expression::Binary(Box::new(lhs), op, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn binary_operator(kind: &TokenKind) -> Option<BinaryOperator> {
+        Some(match kind {
+            TokenKind::Plus => BinaryOperator::Plus,
+            TokenKind::Minus => BinaryOperator::Minus,
+            TokenKind::Asterisk => BinaryOperator::Asterisk,
+            TokenKind::Slash => BinaryOperator::Slash,
+            TokenKind::Percent => BinaryOperator::Percent,
+            TokenKind::ShiftLeft => BinaryOperator::ShiftLeft,
+            TokenKind::ShiftRight => BinaryOperator::ShiftRight,
+            TokenKind::LessThan => BinaryOperator::LessThan,
+            TokenKind::LessThanEquals => BinaryOperator::LessThanEquals,
+            TokenKind::GreaterThan => BinaryOperator::GreaterThan,
+            TokenKind::GreaterThanEquals => BinaryOperator::GreaterThanEquals,
+            TokenKind::EqualsEquals => BinaryOperator::EqualsEquals,
+            TokenKind::BangEquals => BinaryOperator::BangEquals,
+            TokenKind::BitwiseAnd => BinaryOperator::BitwiseAnd,
+            TokenKind::BitwiseXor => BinaryOperator::BitwiseXor,
+            TokenKind::BitwiseOr => BinaryOperator::BitwiseOr,
+            TokenKind::And => BinaryOperator::And,
+            TokenKind::Or => BinaryOperator::Or,
+            TokenKind::Equals => BinaryOperator::Assign,
+            TokenKind::PlusEquals => BinaryOperator::PlusEquals,
+            TokenKind::MinusEquals => BinaryOperator::MinusEquals,
+            TokenKind::AsteriskEquals => BinaryOperator::AsteriskEquals,
+            TokenKind::SlashEquals => BinaryOperator::SlashEquals,
+            TokenKind::PercentEquals => BinaryOperator::PercentEquals,
+            _ => return None,
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression> {
+        let unary = match self.current().kind {
+            TokenKind::Minus => Some(UnaryOperator::Minus),
+            TokenKind::Bang => Some(UnaryOperator::Bang),
+            TokenKind::BitwiseNot => Some(UnaryOperator::BitwiseNot),
+            _ => None,
+        };
+        if let Some(op) = unary {
+            let token = self.advance();
+            let operand = self.parse_unary()?;
+            Ok(Expression::Unary(op, Box::new(operand), token.span.clone()))
+        } else {
+            self.range_expression()
+        }
+    }
+
+    // `..`/`..=` bind more loosely than member/index access but tighter than the
+    // arithmetic operators. Mixing a range with other infix operators is not
+    // rejected: `a..b + c` parses as `(a..b) + c` and `a + b..c` as `a + (b..c)`.
+    fn range_expression(&mut self) -> Result<Expression> {
+        let start = self.postfix_expression()?;
+        if self.check(TokenKind::DotDot) || self.check(TokenKind::DotDotEquals) {
+            let inclusive = self.check(TokenKind::DotDotEquals);
+            self.advance();
+            let end = self.postfix_expression()?;
+            Ok(Expression::Range {
+                start: Box::new(start),
+                end: Box::new(end),
+                inclusive,
+            })
+        } else {
+            Ok(start)
+        }
+    }
+
+    fn postfix_expression(&mut self) -> Result<Expression> {
         let expr = self.primary()?;
         if self.check(TokenKind::LeftParenthesis) {
             self.consume(TokenKind::LeftParenthesis)?;
@@ -141,7 +353,7 @@ impl Parser {
             self.consume(TokenKind::Dot)?;
             Ok(Expression::Access(
                 spanned(Box::new(expr.clone()), expr.span().clone()),
-                spanned(Box::new(self.expression()?), expr.span().clone()),
+                spanned(Box::new(self.postfix_expression()?), expr.span().clone()),
             ))
         } else {
             Ok(expr)
@@ -151,19 +363,131 @@ impl Parser {
     fn primary(&mut self) -> Result<Expression> {
         let token = self.advance();
         match token.kind {
-            TokenKind::Identifier => Ok(Expression::Identifier(spanned(
-                token.lexeme.to_string().clone(),
-                token.span.clone(),
-            ))),
+            TokenKind::Identifier => Ok(Expression::Identifier(
+                spanned(token.lexeme.to_string().clone(), token.span.clone()),
+                None,
+            )),
             TokenKind::LeftParenthesis => {
                 let expr = self.expression()?;
                 self.consume(TokenKind::RightParenthesis)?;
                 Ok(expr)
             }
-            _ => Err(self.error(&token, "Expecting expression")),
+            TokenKind::Fn => self.lambda(token.span.clone()),
+            TokenKind::If => self.if_expression(token.span.clone()),
+            TokenKind::While => self.while_expression(token.span.clone()),
+            TokenKind::Loop => self.loop_expression(token.span.clone()),
+            TokenKind::String => Ok(Expression::Literal(
+                Literal::Str(token.lexeme.to_string()),
+                token.span.clone(),
+            )),
+            TokenKind::Character => Ok(Expression::Literal(
+                Literal::Char(token.lexeme.chars().next().expect(
+                    "the tokenizer only ever emits a character literal with exactly one char",
+                )),
+                token.span.clone(),
+            )),
+            TokenKind::Integer => {
+                let value = self.parse_integer_literal(&token)?;
+                Ok(Expression::Literal(Literal::Int(value), token.span.clone()))
+            }
+            TokenKind::Floating => {
+                let digits: String = token.lexeme.chars().filter(|c| *c != '_').collect();
+                let value = digits
+                    .parse::<f64>()
+                    .map_err(|_| self.error(&token, "invalid floating point literal"))?;
+                Ok(Expression::Literal(Literal::Float(value), token.span.clone()))
+            }
+            _ => Err(self.expected_error(&token, PRIMARY_EXPECTED.to_vec())),
         }
     }
 
+    /// Strips `_` digit separators and recognizes the `0x`/`0b`/`0o` radix prefixes
+    /// the tokenizer already accepts, surfacing overflow or a malformed lexeme as an
+    /// `Error` rather than unwinding the whole compiler.
+    fn parse_integer_literal(&self, token: &Token) -> Result<i64> {
+        let digits: String = token.lexeme.chars().filter(|c| *c != '_').collect();
+        let (radix, digits) = if let Some(rest) = digits.strip_prefix("0x").or(digits.strip_prefix("0X")) {
+            (16, rest)
+        } else if let Some(rest) = digits.strip_prefix("0o").or(digits.strip_prefix("0O")) {
+            (8, rest)
+        } else if let Some(rest) = digits.strip_prefix("0b").or(digits.strip_prefix("0B")) {
+            (2, rest)
+        } else {
+            (10, digits.as_str())
+        };
+        i64::from_str_radix(digits, radix)
+            .map_err(|_| self.error(token, "integer literal out of range"))
+    }
+
+    fn lambda(&mut self, start_span: Span) -> Result<Expression> {
+        self.consume(TokenKind::LeftParenthesis)?;
+        let mut parameters = vec![];
+        while !self.check(TokenKind::RightParenthesis) {
+            let name = self.consume(TokenKind::Identifier)?.clone();
+            self.consume(TokenKind::Colon)?;
+            let ty_span = self.current().span.clone();
+            let ty = self.type_()?;
+            parameters.push(Variable {
+                name: spanned(name.lexeme.to_string().clone(), name.span.clone()),
+                ty: spanned(ty, ty_span),
+                initializer: None,
+            });
+            if self.check(TokenKind::Comma) {
+                self.consume(TokenKind::Comma)?;
+            }
+        }
+        self.consume(TokenKind::RightParenthesis)?;
+        let (ty, ty_span) = if self.check(TokenKind::ThinArrow) {
+            self.consume(TokenKind::ThinArrow)?;
+            let ty_span = self.current().span.clone();
+            let ty = self.type_()?;
+            (ty, ty_span)
+        } else {
+            (Type::Unit, self.current().span.clone())
+        };
+        let body = self.block(|parser| parser.statement())?;
+        Ok(Expression::Lambda {
+            parameters,
+            return_type: spanned(ty, ty_span),
+            body,
+            span: start_span,
+        })
+    }
+
+    /// `if cond: <then> [else: <else>]`. `cond` is a plain expression with no
+    /// required parentheses, the same convention `for_`'s iterable already uses.
+    fn if_expression(&mut self, start_span: Span) -> Result<Expression> {
+        let condition = self.expression()?;
+        let then_branch = self.block(|parser| parser.statement())?;
+        let else_branch = if self.check(TokenKind::Else) {
+            self.consume(TokenKind::Else)?;
+            Some(self.block(|parser| parser.statement())?)
+        } else {
+            None
+        };
+        Ok(Expression::If {
+            condition: Box::new(condition),
+            then_branch,
+            else_branch,
+            span: start_span,
+        })
+    }
+
+    fn while_expression(&mut self, start_span: Span) -> Result<Expression> {
+        let condition = self.expression()?;
+        let body = self.block(|parser| parser.statement())?;
+        Ok(Expression::While {
+            condition: Box::new(condition),
+            body,
+            span: start_span,
+        })
+    }
+
+    fn loop_expression(&mut self, start_span: Span) -> Result<Expression> {
+        let body = self.block(|parser| parser.statement())?;
+        Ok(Expression::Loop { body, span: start_span })
+    }
+
     fn type_(&mut self) -> Result<Type> {
         let ty = match self.current().kind {
             TokenKind::Int => {
@@ -207,7 +531,34 @@ impl Parser {
                     Type::Reference(Box::new(ty))
                 }
             }
-            _ => unreachable!(),
+            TokenKind::Fn => {
+                self.consume(TokenKind::Fn)?;
+                self.consume(TokenKind::LeftParenthesis)?;
+                let mut params = vec![];
+                while !self.check(TokenKind::RightParenthesis) {
+                    params.push(self.type_()?);
+                    if self.check(TokenKind::Comma) {
+                        self.consume(TokenKind::Comma)?;
+                    }
+                }
+                self.consume(TokenKind::RightParenthesis)?;
+                self.consume(TokenKind::ThinArrow)?;
+                let ret = self.type_()?;
+                Type::Function(params, Box::new(ret))
+            }
+            _ => {
+                let token = self.current().clone();
+                return Err(self.expected_error(
+                    &token,
+                    vec![
+                        TokenKind::Int,
+                        TokenKind::Float,
+                        TokenKind::Identifier,
+                        TokenKind::BitwiseAnd,
+                        TokenKind::Fn,
+                    ],
+                ));
+            }
         };
         Ok(ty)
     }
@@ -251,14 +602,40 @@ impl Parser {
     }
 
     fn consume(&mut self, kind: TokenKind) -> Result<Token> {
-        if self.check(kind) {
+        if self.check(kind.clone()) {
             Ok(self.advance())
         } else {
-            Err(self.error(self.current(), "Expecting token"))
+            Err(self.expected_error(self.current(), vec![kind]))
         }
     }
 
     fn error(&self, token: &Token, message: &str) -> crate::error::Error {
-        Error::new(message.to_string(), token.span.clone())
+        Error::new(message.to_string(), token.span.clone(), self.source_map.clone())
     }
+
+    /// Builds an error reporting that `token` matched none of `expected`,
+    /// carrying the full set alongside the rendered message (see
+    /// `Error::expected`) instead of leaving it to be reconstructed from text.
+    fn expected_error(&self, token: &Token, expected: Vec<TokenKind>) -> crate::error::Error {
+        let message = match expected.as_slice() {
+            [kind] => format!("expected {:?}, but got {:?}", kind, token.kind),
+            kinds => format!("expected one of {:?}, but got {:?}", kinds, token.kind),
+        };
+        self.error(token, &message).with_expected(expected)
+    }
+}
+
+/// Serializes a parsed program to JSON, so external tooling (formatters, LSP
+/// servers, test-snapshot harnesses, cross-process compiler stages) can consume
+/// or cache the tree without linking against this crate's internals.
+#[cfg(feature = "serde")]
+pub(crate) fn to_json(statements: &[Statement]) -> serde_json::Result<String> {
+    serde_json::to_string(statements)
+}
+
+/// The other direction of `to_json`: reconstructs a parsed program from JSON it
+/// produced.
+#[cfg(feature = "serde")]
+pub(crate) fn from_json(json: &str) -> serde_json::Result<Vec<Statement>> {
+    serde_json::from_str(json)
 }
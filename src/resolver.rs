@@ -0,0 +1,208 @@
+//! A post-parse pass over the AST that annotates every `Expression::Identifier`
+//! with how many enclosing scopes to walk to find its binding (see the `depth`
+//! field added in `ast.rs`), and reports unresolved or re-declared names as
+//! `Error`s the same way `Tokenizer`/`Parser` collect theirs, instead of
+//! stopping at the first one.
+use std::collections::HashMap;
+
+use crate::{
+    ast::{Block, Expression, Statement},
+    error::Error,
+    span::{SharedSourceMap, Span, Spanned},
+};
+
+/// One lexical scope: the names declared directly in it, each remembered with
+/// the span of its declaration.
+#[derive(Default)]
+struct Scope {
+    bindings: HashMap<String, Span>,
+}
+
+pub(crate) struct Resolver {
+    scopes: Vec<Scope>,
+    source_map: SharedSourceMap,
+    errors: Vec<Error>,
+}
+
+impl Resolver {
+    /// Resolves every identifier in `statements` in place, returning the
+    /// collected errors (re-declarations, undeclared names) instead of failing
+    /// at the first one.
+    pub(crate) fn resolve(
+        statements: &mut [Statement],
+        source_map: SharedSourceMap,
+    ) -> std::result::Result<(), Vec<Error>> {
+        let mut resolver = Resolver {
+            scopes: vec![Scope::default()],
+            source_map,
+            errors: Vec::new(),
+        };
+        for statement in statements {
+            resolver.resolve_statement(statement);
+        }
+        if resolver.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(resolver.errors)
+        }
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(Scope::default());
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Declares `name` in the current scope. Shadowing an *outer* scope is
+    /// normal and not reported; re-declaring a name already bound in the same
+    /// scope is reported, since that's almost always a typo rather than intent.
+    fn declare(&mut self, name: &Spanned<String>) {
+        let scope = self
+            .scopes
+            .last_mut()
+            .expect("Resolver always has at least one scope");
+        if scope.bindings.contains_key(&name.0) {
+            self.errors.push(Error::new(
+                format!("'{}' shadows an earlier binding in the same scope", name.0),
+                name.1.clone(),
+                self.source_map.clone(),
+            ));
+        }
+        scope.bindings.insert(name.0.clone(), name.1.clone());
+    }
+
+    /// Walks the scope stack from innermost to outermost looking for `name`,
+    /// returning the number of enclosing scopes crossed (`0` means it was
+    /// found in the current scope), or `None` if it's undeclared.
+    fn depth_of(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .position(|scope| scope.bindings.contains_key(name))
+    }
+
+    fn resolve_block(&mut self, block: &mut Block<Statement>) {
+        self.push_scope();
+        for statement in &mut block.ts {
+            self.resolve_statement(statement);
+        }
+        self.pop_scope();
+    }
+
+    fn resolve_statement(&mut self, statement: &mut Statement) {
+        match statement {
+            Statement::Import(path, alias) => {
+                let binding = alias.clone().unwrap_or_else(|| {
+                    let last_segment = path.0.rsplit('/').next().unwrap_or(&path.0).to_string();
+                    (last_segment, path.1.clone())
+                });
+                self.declare(&binding);
+            }
+            Statement::Struct(name, fields) => {
+                self.declare(name);
+                for field in &mut fields.ts {
+                    if let Some(initializer) = &mut field.initializer {
+                        self.resolve_expression(&mut initializer.0);
+                    }
+                }
+            }
+            Statement::Function(name, params, _return_type, body) => {
+                self.declare(name);
+                self.push_scope();
+                for param in params.iter() {
+                    self.declare(&param.name);
+                }
+                for statement in &mut body.ts {
+                    self.resolve_statement(statement);
+                }
+                self.pop_scope();
+            }
+            Statement::For(name, iterable, body) => {
+                self.resolve_expression(iterable);
+                self.push_scope();
+                self.declare(name);
+                for statement in &mut body.ts {
+                    self.resolve_statement(statement);
+                }
+                self.pop_scope();
+            }
+            Statement::Return(value) => {
+                if let Some(expr) = value {
+                    self.resolve_expression(expr);
+                }
+            }
+            Statement::Declaration { variable, .. } => {
+                if let Some(initializer) = &mut variable.initializer {
+                    self.resolve_expression(&mut initializer.0);
+                }
+                self.declare(&variable.name);
+            }
+            Statement::Expression(expr) => self.resolve_expression(expr),
+        }
+    }
+
+    fn resolve_expression(&mut self, expression: &mut Expression) {
+        match expression {
+            Expression::Identifier(name, depth) => match self.depth_of(&name.0) {
+                Some(found) => *depth = Some(found),
+                None => self.errors.push(Error::new(
+                    format!("use of undeclared name '{}'", name.0),
+                    name.1.clone(),
+                    self.source_map.clone(),
+                )),
+            },
+            Expression::Call(callee, args) => {
+                self.resolve_expression(&mut callee.0);
+                for arg in args {
+                    self.resolve_expression(&mut arg.0);
+                }
+            }
+            // The right-hand side of `.` is a field name, not a variable
+            // reference, so only the object expression is resolved.
+            Expression::Access(expr, _field) => self.resolve_expression(&mut expr.0),
+            Expression::Range { start, end, .. } => {
+                self.resolve_expression(start);
+                self.resolve_expression(end);
+            }
+            Expression::Lambda {
+                parameters, body, ..
+            } => {
+                self.push_scope();
+                for parameter in parameters.iter() {
+                    self.declare(&parameter.name);
+                }
+                for statement in &mut body.ts {
+                    self.resolve_statement(statement);
+                }
+                self.pop_scope();
+            }
+            Expression::Binary(lhs, _, rhs) => {
+                self.resolve_expression(lhs);
+                self.resolve_expression(rhs);
+            }
+            Expression::Unary(_, operand, _) => self.resolve_expression(operand),
+            Expression::Literal(..) => {}
+            Expression::If {
+                condition,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                self.resolve_expression(condition);
+                self.resolve_block(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.resolve_block(else_branch);
+                }
+            }
+            Expression::While {
+                condition, body, ..
+            } => {
+                self.resolve_expression(condition);
+                self.resolve_block(body);
+            }
+            Expression::Loop { body, .. } => self.resolve_block(body),
+        }
+    }
+}
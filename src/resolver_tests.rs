@@ -0,0 +1,69 @@
+//! `Resolver::resolve` annotates each `Expression::Identifier`'s `depth` in
+//! place and reports undeclared names and same-scope re-declarations as
+//! `Error`s, so these tests parse a fixture and inspect the resolved tree
+//! rather than comparing it against a hand-built expected AST.
+#![cfg(test)]
+
+use std::rc::Rc;
+
+use crate::{
+    ast::{Expression, Statement},
+    parser::Parser,
+    resolver::Resolver,
+    span::SourceMap,
+    tokenizer::Tokenizer,
+};
+
+fn parse(source: &str) -> Vec<Statement> {
+    let source_map = SourceMap::shared();
+    let file = source_map
+        .borrow_mut()
+        .add_file("<resolver>".to_string(), source.to_string());
+    let mut tokenizer = Tokenizer::new(file, Rc::new(source.to_string()), source_map.clone());
+    let tokens = tokenizer
+        .tokenize()
+        .expect("fixture must tokenize cleanly");
+    Parser::new(tokens, source_map)
+        .parse()
+        .expect("fixture must parse cleanly")
+}
+
+#[test]
+fn resolves_local_variable_to_its_declaring_scope() {
+    let mut statements = parse("var x = 1\nx\n");
+    let source_map = SourceMap::shared();
+    Resolver::resolve(&mut statements, source_map).expect("both uses of 'x' are declared");
+
+    match &statements[1] {
+        Statement::Expression(Expression::Identifier(_, depth)) => {
+            assert_eq!(*depth, Some(0), "'x' is declared in the same top-level scope it's used in")
+        }
+        other => panic!("expected an identifier expression, got {:?}", other),
+    }
+}
+
+#[test]
+fn reports_use_of_undeclared_name() {
+    let mut statements = parse("x\n");
+    let source_map = SourceMap::shared();
+    let errors = Resolver::resolve(&mut statements, source_map)
+        .expect_err("'x' was never declared anywhere");
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn reports_redeclaration_in_the_same_scope() {
+    let mut statements = parse("var x = 1\nvar x = 2\n");
+    let source_map = SourceMap::shared();
+    let errors = Resolver::resolve(&mut statements, source_map)
+        .expect_err("the second 'var x' shadows the first in the same scope");
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn function_parameter_is_visible_inside_its_own_body() {
+    let mut statements = parse("fn add(a: int, b: int) -> int:\n    return a + b\n");
+    let source_map = SourceMap::shared();
+    Resolver::resolve(&mut statements, source_map)
+        .expect("'a' and 'b' are declared as this function's parameters");
+}
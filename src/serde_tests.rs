@@ -0,0 +1,51 @@
+//! Round-trips a parsed program through `serde_json` and checks that the
+//! deserialized tree matches the original, ignoring spans. Only meaningful with
+//! the `serde` feature enabled.
+#![cfg(feature = "serde")]
+
+use std::rc::Rc;
+
+use crate::{
+    parser::Parser,
+    span::{assert_eq_ignore_span, SourceMap},
+    tokenizer::{Token, Tokenizer},
+};
+
+#[test]
+fn round_trips_through_json() {
+    let source = "var x = 1\n";
+    let source_map = SourceMap::shared();
+    let file = source_map
+        .borrow_mut()
+        .add_file("<roundtrip>".to_string(), source.to_string());
+    let mut tokenizer = Tokenizer::new(file, Rc::new(source.to_string()), source_map.clone());
+    let tokens = tokenizer.tokenize().expect("fixture must tokenize cleanly");
+    let statements = Parser::new(tokens, source_map)
+        .parse()
+        .expect("fixture must parse cleanly");
+
+    let json = crate::parser::to_json(&statements).expect("AST is always serializable");
+    let round_tripped = crate::parser::from_json(&json).expect("AST JSON is always deserializable");
+
+    assert_eq_ignore_span(&statements, &round_tripped);
+}
+
+/// `Token::lexeme` is an `Rc<String>`, which has no `Serialize`/`Deserialize` impl
+/// of its own — this only compiles (and passes) once that's bridged with a custom
+/// `#[serde(with = ...)]`.
+#[test]
+fn round_trips_tokens_through_json() {
+    let source = "var x = 1\n";
+    let source_map = SourceMap::shared();
+    let file = source_map
+        .borrow_mut()
+        .add_file("<roundtrip-tokens>".to_string(), source.to_string());
+    let mut tokenizer = Tokenizer::new(file, Rc::new(source.to_string()), source_map.clone());
+    let tokens = tokenizer.tokenize().expect("fixture must tokenize cleanly");
+
+    let json = serde_json::to_string(&tokens).expect("tokens are always serializable");
+    let round_tripped: Vec<Token> =
+        serde_json::from_str(&json).expect("token JSON is always deserializable");
+
+    assert_eq_ignore_span(&tokens, &round_tripped);
+}
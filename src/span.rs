@@ -1,8 +1,214 @@
-use std::{ops::Range, rc::Rc};
-
-pub(crate) type Span = (Rc<String>, Range<(usize, usize)>); // (filename, range<line, start/end>)
-pub(crate) type Spanned<T> = (T, Span);
-
-pub(crate) fn spanned<T>(t: T, span: Span) -> Spanned<T> {
-    (t, span)
-}
+use std::{cell::RefCell, io::IsTerminal, ops::Range, rc::Rc};
+
+/// Controls whether rendered diagnostics include ANSI color codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum Color {
+    Always,
+    Never,
+    #[default]
+    Auto,
+}
+
+impl Color {
+    /// Resolves `Auto` against whether stdout is a terminal and `NO_COLOR` is
+    /// unset, per <https://no-color.org>.
+    pub(crate) fn enabled(self) -> bool {
+        match self {
+            Color::Always => true,
+            Color::Never => false,
+            Color::Auto => std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+        }
+    }
+}
+
+pub(crate) type FileId = usize;
+pub(crate) type Span = (FileId, Range<usize>);
+pub(crate) type Spanned<T> = (T, Span);
+
+/// How many columns a `\t` counts as, shared by `Tokenizer`'s indent-depth scan
+/// and `SourceFile::line_col`'s column resolution, so a tab-indented file reports
+/// the same column from either path instead of the two silently disagreeing.
+pub(crate) const DEFAULT_TAB_WIDTH: usize = 4;
+
+pub(crate) fn spanned<T>(t: T, span: Span) -> Spanned<T> {
+    (t, span)
+}
+
+/// Combines two spans into the smallest span covering both, for parser rules that
+/// span several tokens (e.g. a binary expression's left operand through its
+/// right). Both spans are assumed to be in the same file; a mismatch is only
+/// checked in debug builds, since recovering wrong-file spans isn't worth a panic
+/// in release.
+pub(crate) fn join(a: &Span, b: &Span) -> Span {
+    debug_assert_eq!(a.0, b.0, "joining spans from different files");
+    let start = a.1.start.min(b.1.start);
+    let end = a.1.end.max(b.1.end);
+    (a.0, start..end)
+}
+
+/// Folds an iterator of spans into the span enclosing all of them: the first
+/// span's start through the last span's end.
+pub(crate) fn join_all(mut spans: impl Iterator<Item = Span>) -> Span {
+    let first = spans.next().expect("join_all requires at least one span");
+    spans.fold(first, |acc, span| join(&acc, &span))
+}
+
+/// Structural equality that ignores `Span` positions, so expected ASTs in tests
+/// can be written with throwaway spans instead of exact byte offsets.
+pub(crate) trait EqIgnoreSpan {
+    fn eq_ignore_span(&self, other: &Self) -> bool;
+}
+
+impl<T: EqIgnoreSpan> EqIgnoreSpan for Spanned<T> {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        self.0.eq_ignore_span(&other.0)
+    }
+}
+
+impl<T: EqIgnoreSpan> EqIgnoreSpan for Option<T> {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.eq_ignore_span(b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: EqIgnoreSpan> EqIgnoreSpan for Box<T> {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        (**self).eq_ignore_span(&**other)
+    }
+}
+
+impl<T: EqIgnoreSpan> EqIgnoreSpan for Vec<T> {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.eq_ignore_span(b))
+    }
+}
+
+macro_rules! eq_ignore_span_via_partial_eq {
+    ($($ty:ty),* $(,)?) => {
+        $(impl EqIgnoreSpan for $ty {
+            fn eq_ignore_span(&self, other: &Self) -> bool {
+                self == other
+            }
+        })*
+    };
+}
+
+eq_ignore_span_via_partial_eq!(bool, i64, f64, String);
+
+/// Asserts that `actual` structurally matches `expected`, ignoring spans, and
+/// prints both trees (with their real spans) if they don't.
+#[cfg(test)]
+pub(crate) fn assert_eq_ignore_span<T: EqIgnoreSpan + std::fmt::Debug>(actual: &T, expected: &T) {
+    assert!(
+        actual.eq_ignore_span(expected),
+        "AST mismatch (ignoring spans):\n  actual:   {:?}\n  expected: {:?}",
+        actual,
+        expected
+    );
+}
+
+#[derive(Debug)]
+struct SourceFile {
+    name: String,
+    contents: String,
+    line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+    fn new(name: String, contents: String) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(contents.match_indices('\n').map(|(i, _)| i + 1));
+        Self {
+            name,
+            contents,
+            line_starts,
+        }
+    }
+
+    /// Columns are char counts, not byte offsets, so carets line up under multibyte
+    /// characters instead of drifting past them — except `\t`, which counts as
+    /// `DEFAULT_TAB_WIDTH` columns instead of one, matching how far the tokenizer's
+    /// indent-depth scan treats a tab as moving.
+    fn line_col(&self, offset: usize) -> (usize, usize) {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next) => next - 1,
+        };
+        let start = self.line_starts[line];
+        let col = self.contents[start..offset]
+            .chars()
+            .map(|c| if c == '\t' { DEFAULT_TAB_WIDTH } else { 1 })
+            .sum::<usize>()
+            + 1;
+        (line + 1, col)
+    }
+
+    fn line_text(&self, line: usize) -> &str {
+        let start = self.line_starts[line - 1];
+        let end = self
+            .line_starts
+            .get(line)
+            .map(|&s| s - 1)
+            .unwrap_or(self.contents.len());
+        &self.contents[start..end]
+    }
+}
+
+/// Registers source files by a base offset-free `FileId` and recovers line/column
+/// information from byte offsets on demand, rather than tracking it while lexing.
+#[derive(Debug, Default)]
+pub(crate) struct SourceMap {
+    files: Vec<SourceFile>,
+    color: Color,
+}
+
+pub(crate) type SharedSourceMap = Rc<RefCell<SourceMap>>;
+
+impl SourceMap {
+    pub(crate) fn new() -> Self {
+        Self {
+            files: Vec::new(),
+            color: Color::default(),
+        }
+    }
+
+    pub(crate) fn shared() -> SharedSourceMap {
+        Rc::new(RefCell::new(Self::new()))
+    }
+
+    pub(crate) fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    pub(crate) fn color_enabled(&self) -> bool {
+        self.color.enabled()
+    }
+
+    pub(crate) fn add_file(&mut self, name: String, contents: String) -> FileId {
+        self.files.push(SourceFile::new(name, contents));
+        self.files.len() - 1
+    }
+
+    pub(crate) fn filename(&self, file: FileId) -> &str {
+        &self.files[file].name
+    }
+
+    /// Resolves a byte-offset span into its 1-based (start line, start column, end
+    /// line, end column). A span that crosses multiple lines has `start_line !=
+    /// end_line`; callers that only handle single-line spans can compare the two.
+    pub(crate) fn resolve(&self, span: &Span) -> (usize, usize, usize, usize) {
+        let file = &self.files[span.0];
+        let (start_line, start_col) = file.line_col(span.1.start);
+        let (end_line, end_col) = file.line_col(span.1.end.max(span.1.start));
+        (start_line, start_col, end_line, end_col)
+    }
+
+    /// The text of a single 1-based line number, with no trailing newline.
+    pub(crate) fn line_text(&self, file: FileId, line: usize) -> &str {
+        self.files[file].line_text(line)
+    }
+}
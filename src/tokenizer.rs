@@ -1,25 +1,32 @@
+use crate::diagnostic::DiagnosticFrame;
 use crate::error::{Error, Result};
-use crate::span::Span;
+use crate::span::{EqIgnoreSpan, FileId, SharedSourceMap, Span, DEFAULT_TAB_WIDTH};
 use std::rc::Rc;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum TokenKind {
     // literals
     Identifier, // abc
     String,     // "abc"
+    Character,  // 'a'
     Integer,    // 123, 0x123, 0b1010, 17e+2, 17e-2, -123
     Floating,   // 123.456, 123.456e+2, 123.456e-2, -123.456
     // keywords
     As,     // as
     Const,  // const
+    Else,   // else
     Fn,     // fn
     For,    // for
-    In,     // in
+    If,     // if
     Import, // import
+    In,     // in
+    Loop,   // loop
     Mut,    // mut
     Return, // return
     Struct, // struct
     Var,    // var
+    While,  // while
     // types
     Float, // float
     Int,   // int
@@ -32,6 +39,8 @@ pub(crate) enum TokenKind {
     RightBracket,     // ]
     Comma,            // ,
     Dot,              // .
+    DotDot,           // ..
+    DotDotEquals,     // ..=
     Colon,            // :
     // Semicolon,        // ;
     ThinArrow, // ->
@@ -52,8 +61,10 @@ pub(crate) enum TokenKind {
     BangEquals,        // !=
     LessThan,          // <
     LessThanEquals,    // <=
+    ShiftLeft,         // <<
     GreaterThan,       // >
     GreaterThanEquals, // >=
+    ShiftRight,        // >>
     // logical operators
     And, // &&
     Or,  // ||
@@ -69,270 +80,533 @@ pub(crate) enum TokenKind {
     Eof,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub(crate) struct Token {
     pub(crate) kind: TokenKind,
+    #[cfg_attr(feature = "serde", serde(with = "lexeme_serde"))]
     pub(crate) lexeme: Rc<String>,
     pub(crate) span: Span,
 }
 
+/// `Rc<String>` has no `Serialize`/`Deserialize` impl on its own (that needs
+/// serde's `rc` feature, which turns every `Rc` into a shared/cyclic-aware
+/// pointer graph — overkill for a lexeme, which is never actually shared data).
+/// Serializing as a plain string and re-wrapping in a fresh `Rc` on the way back
+/// is all a lexeme needs.
+#[cfg(feature = "serde")]
+mod lexeme_serde {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::rc::Rc;
+
+    pub(crate) fn serialize<S: Serializer>(lexeme: &Rc<String>, serializer: S) -> Result<S::Ok, S::Error> {
+        lexeme.as_str().serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Rc<String>, D::Error> {
+        String::deserialize(deserializer).map(Rc::new)
+    }
+}
+
 impl Token {
     pub(crate) fn new(kind: TokenKind, lexeme: Rc<String>, span: Span) -> Token {
         Token { kind, lexeme, span }
     }
 }
 
-pub(crate) struct Tokenizer {
-    filename: Rc<String>,
+impl EqIgnoreSpan for TokenKind {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl EqIgnoreSpan for Token {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.lexeme == other.lexeme
+    }
+}
+
+/// Wraps the source bytes and the scan offset behind `peek`/`peek_second`/
+/// `bump`, so every tokenizing arm advances through one funnel instead of poking
+/// an index field directly. Diagnostics resolve line/column from a byte offset
+/// separately, on demand, via `SourceFile::line_col` (see `span.rs`) — there's
+/// no incremental line/column tracked here to keep in sync with it.
+struct Cursor {
     contents: Rc<String>,
-    index: usize,
-    line: usize,
-    column: usize,
-    indent_stack: Vec<(usize, bool)>, // (indent, continuation)
+    offset: usize,
+}
+
+impl Cursor {
+    fn new(contents: Rc<String>) -> Cursor {
+        Cursor { contents, offset: 0 }
+    }
+
+    fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.peek_at(0)
+    }
+
+    fn peek_second(&self) -> Option<u8> {
+        self.peek_at(1)
+    }
+
+    fn peek_at(&self, ahead: usize) -> Option<u8> {
+        self.contents.as_bytes().get(self.offset() + ahead).copied()
+    }
+
+    /// Decodes the UTF-8 sequence starting at the current offset and returns it
+    /// along with its byte length, so literals can interpret (rather than just
+    /// copy) non-ASCII content.
+    fn peek_char(&self) -> Option<(char, usize)> {
+        self.contents[self.offset()..].chars().next().map(|c| (c, c.len_utf8()))
+    }
+
+    fn bump(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.offset += 1;
+        Some(byte)
+    }
+
+    fn bump_n(&mut self, n: usize) {
+        for _ in 0..n {
+            self.bump();
+        }
+    }
+}
+
+/// Scans the source as raw bytes rather than `chars().nth(index)`: every punctuation,
+/// keyword, number, and operator form is pure ASCII, so byte dispatch is both
+/// faster than decoding UTF-8 codepoint-by-codepoint and makes the cursor's offset
+/// an exact byte offset instead of a char-index-used-as-a-byte-offset footgun.
+/// Only the identifier and string/character arms decode non-ASCII text, one
+/// `char` at a time via `Cursor::peek_char`; every other arm dispatches on a
+/// single ASCII byte.
+pub(crate) struct Tokenizer {
+    file: FileId,
+    cursor: Cursor,
+    indent_stack: Vec<(usize, bool, Span)>, // (indent, continuation, span of the line that introduced it)
+    source_map: SharedSourceMap,
+    /// How many columns of indentation a `\t` is worth in the indent-depth scan
+    /// below. See `DEFAULT_TAB_WIDTH`.
+    tab_width: usize,
 }
 
 impl Tokenizer {
-    pub(crate) fn new(filename: Rc<String>, contents: String) -> Tokenizer {
+    pub(crate) fn new(file: FileId, contents: Rc<String>, source_map: SharedSourceMap) -> Tokenizer {
+        Self::with_tab_width(file, contents, source_map, DEFAULT_TAB_WIDTH)
+    }
+
+    /// Same as `new`, but with an explicit tab width instead of `DEFAULT_TAB_WIDTH`
+    /// — for sources whose indentation convention disagrees with it.
+    pub(crate) fn with_tab_width(
+        file: FileId,
+        contents: Rc<String>,
+        source_map: SharedSourceMap,
+        tab_width: usize,
+    ) -> Tokenizer {
         Tokenizer {
-            filename,
-            contents: Rc::new(contents.clone()),
-            index: 0,
-            line: 1,
-            column: 1,
-            indent_stack: vec![(0, false)],
+            file,
+            cursor: Cursor::new(contents),
+            indent_stack: vec![(0, false, (file, 0..0))],
+            source_map,
+            tab_width,
         }
     }
 
     fn single_token(&mut self, kind: TokenKind) -> Result<Token> {
-        let token = Token::new(kind, Rc::new("".to_string()), self.construct_span(1));
-        self.index += 1;
-        self.column += 1;
+        let span = self.span_from(self.cursor.offset(), 1);
+        let token = Token::new(kind, Rc::new("".to_string()), span);
+        self.cursor.bump();
         Ok(token)
     }
 
     fn double_token(
         &mut self,
-        char_1: char,
+        byte_1: u8,
         kind_1: TokenKind,
-        char_2: char,
+        byte_2: u8,
         kind_2: TokenKind,
     ) -> Result<Token> {
-        self.index += 1;
-        self.column += 1;
-        let token = if self.current().unwrap() == char_2 {
-            self.index += 1;
-            self.column += 1;
+        let start = self.cursor.offset();
+        self.cursor.bump();
+        let token = if self.current_byte().unwrap() == byte_2 {
+            self.cursor.bump();
             Token::new(
                 kind_2,
-                Rc::new(format!("{}{}", char_1, char_2)),
-                self.construct_span(2),
+                Rc::new(format!("{}{}", byte_1 as char, byte_2 as char)),
+                self.span_from(start, 2),
             )
         } else {
-            Token::new(kind_1, Rc::new(char_1.to_string()), self.construct_span(1))
+            Token::new(
+                kind_1,
+                Rc::new((byte_1 as char).to_string()),
+                self.span_from(start, 1),
+            )
         };
         Ok(token)
     }
 
-    fn error(&self, message: &str, span: Span) -> Error {
-        Error::new(message, span)
+    fn error_frame(&self, frame: DiagnosticFrame) -> Error {
+        Error::from_frame(frame, self.source_map.clone())
+    }
+
+    /// Builds an absolute byte-offset span starting at `start` and covering `length`
+    /// indices; line/column are recovered lazily from this when a diagnostic is rendered.
+    fn span_from(&self, start: usize, length: usize) -> Span {
+        (self.file, start..start + length)
+    }
+
+    fn current_byte(&self) -> Option<u8> {
+        self.cursor.peek()
+    }
+
+    fn current_char(&self) -> Option<(char, usize)> {
+        self.cursor.peek_char()
     }
 
-    fn construct_span(&self, length: usize) -> Span {
-        let start = (self.line, self.column);
-        let end = (self.line, self.column + length);
-        (self.filename.clone(), start..end)
+    /// Decodes one escape, with the leading `\` already consumed. Shared by string
+    /// and character literals so `\xNN`/`\u{...}` only need to be gotten right once.
+    fn escape_sequence(&mut self) -> Result<char> {
+        match self.current_byte() {
+            Some(b'n') => self.consume_escape('\n'),
+            Some(b'r') => self.consume_escape('\r'),
+            Some(b't') => self.consume_escape('\t'),
+            Some(b'0') => self.consume_escape('\0'),
+            Some(b'\\') => self.consume_escape('\\'),
+            Some(b'"') => self.consume_escape('"'),
+            Some(b'\'') => self.consume_escape('\''),
+            Some(b'x') => {
+                self.cursor.bump();
+                let start = self.cursor.offset();
+                let value = self.hex_digits(2)?;
+                char::from_u32(value).ok_or_else(|| {
+                    self.error_frame(
+                        DiagnosticFrame::error("invalid `\\x` escape")
+                            .with_label(self.span_from(start, 2), "not a valid codepoint"),
+                    )
+                })
+            }
+            Some(b'u') => {
+                let escape_start = self.cursor.offset() - 1;
+                self.cursor.bump();
+                if self.current_byte() != Some(b'{') {
+                    return Err(self.error_frame(
+                        DiagnosticFrame::error("invalid `\\u` escape")
+                            .with_label(self.span_from(self.cursor.offset(), 1), "expected `{` after `\\u`"),
+                    ));
+                }
+                self.cursor.bump();
+                let digits_start = self.cursor.offset();
+                while self.current_byte().is_some_and(|b| (b as char).is_ascii_hexdigit()) {
+                    self.cursor.bump();
+                }
+                let value = u32::from_str_radix(&self.cursor.contents[digits_start..self.cursor.offset()], 16)
+                    .expect("only consumed ASCII hex digits");
+                if self.current_byte() != Some(b'}') {
+                    return Err(self.error_frame(
+                        DiagnosticFrame::error("invalid `\\u` escape")
+                            .with_label(
+                                self.span_from(escape_start, self.cursor.offset() - escape_start),
+                                "expected closing `}`",
+                            ),
+                    ));
+                }
+                self.cursor.bump();
+                char::from_u32(value).ok_or_else(|| {
+                    self.error_frame(
+                        DiagnosticFrame::error("invalid `\\u` escape")
+                            .with_label(
+                                self.span_from(escape_start, self.cursor.offset() - escape_start),
+                                "not a valid codepoint",
+                            ),
+                    )
+                })
+            }
+            _ => Err(self.error_frame(
+                DiagnosticFrame::error("illegal escape sequence")
+                    .with_label(self.span_from(self.cursor.offset(), 1), "this escape isn't recognized")
+                    .with_hint(
+                        "supported escapes are \\n, \\r, \\t, \\0, \\\\, \\\", \\', \\xNN, and \\u{...}",
+                    ),
+            )),
+        }
     }
 
-    fn current(&self) -> Option<char> {
-        self.contents.chars().nth(self.index)
+    fn consume_escape(&mut self, decoded: char) -> Result<char> {
+        self.cursor.bump();
+        Ok(decoded)
     }
 
-    pub(crate) fn tokenize(&mut self) -> Result<Vec<Token>> {
+    /// Reads exactly `count` hex digits starting at the current position.
+    fn hex_digits(&mut self, count: usize) -> Result<u32> {
+        let start = self.cursor.offset();
+        let mut value: u32 = 0;
+        for _ in 0..count {
+            match self.current_byte().and_then(|b| (b as char).to_digit(16)) {
+                Some(digit) => {
+                    value = value * 16 + digit;
+                    self.cursor.bump();
+                }
+                None => {
+                    return Err(self.error_frame(
+                        DiagnosticFrame::error("invalid `\\x` escape")
+                            .with_label(self.span_from(start, self.cursor.offset() - start + 1), "expected two hex digits"),
+                    ))
+                }
+            }
+        }
+        Ok(value)
+    }
+
+    /// Points at the opening quote, with a secondary label at the point the scan
+    /// gave up, since that's where the author's mismatched quote usually is.
+    fn unterminated_literal_error(&self, kind: &str, start: usize) -> Error {
+        self.error_frame(
+            DiagnosticFrame::error(format!("unterminated {} literal", kind))
+                .with_label(self.span_from(start, 1), format!("{} literal starts here", kind))
+                .with_label(self.span_from(self.cursor.offset(), 1), "expected a closing quote before here"),
+        )
+    }
+
+    /// Keeps scanning after a bad character or malformed literal instead of bailing,
+    /// so a single typo doesn't hide every other lexer error in the file. Mirrors
+    /// `Parser::parse`'s `Vec<Error>` recovery.
+    pub(crate) fn tokenize(&mut self) -> std::result::Result<Vec<Token>, Vec<Error>> {
         let mut tokens = Vec::new();
+        let mut errors = Vec::new();
+        loop {
+            let index_before = self.cursor.offset();
+            match self.next_token() {
+                Ok(token) => {
+                    let is_eof = token.kind == TokenKind::Eof;
+                    tokens.push(token);
+                    if is_eof {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    errors.push(err);
+                    // Most error paths already advance past the offending text; this
+                    // only guards the ones that don't, so we can't loop forever.
+                    if self.cursor.offset() == index_before {
+                        self.cursor.bump();
+                    }
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(tokens)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Scans the rest of an identifier whose first character has already been
+    /// consumed at `start`. Continuation characters are any `XID_Continue`
+    /// character, not just ASCII (approximated here via `char::is_alphanumeric`,
+    /// since this tree has no `Cargo.toml` to pull in `unicode-xid`), so
+    /// identifiers like `café` tokenize as a single `Identifier`. Keyword matching
+    /// stays ASCII-only since no keyword contains a non-ASCII character.
+    fn identifier(&mut self, start: usize) -> Token {
         loop {
-            let token = self.next_token()?;
-            if token.kind == TokenKind::Eof {
-                tokens.push(token);
-                break;
+            match self.cursor.peek_char() {
+                Some((c, len)) if c == '_' || c.is_alphanumeric() => {
+                    self.cursor.bump_n(len);
+                }
+                _ => break,
             }
-            tokens.push(token);
         }
-        Ok(tokens)
+        let value = self.cursor.contents[start..self.cursor.offset()].to_string();
+        let kind = match value.as_str() {
+            "as" => TokenKind::As,
+            "const" => TokenKind::Const,
+            "else" => TokenKind::Else,
+            "fn" => TokenKind::Fn,
+            "for" => TokenKind::For,
+            "if" => TokenKind::If,
+            "import" => TokenKind::Import,
+            "in" => TokenKind::In,
+            "loop" => TokenKind::Loop,
+            "mut" => TokenKind::Mut,
+            "return" => TokenKind::Return,
+            "struct" => TokenKind::Struct,
+            "var" => TokenKind::Var,
+            "while" => TokenKind::While,
+            "float" => TokenKind::Float,
+            "int" => TokenKind::Int,
+            _ => TokenKind::Identifier,
+        };
+        Token::new(kind, Rc::new(value.clone()), self.span_from(start, value.len()))
     }
 
     fn next_token(&mut self) -> Result<Token> {
-        if let None = self.current() {
+        let Some(b) = self.current_byte() else {
             return Ok(Token::new(
                 TokenKind::Eof,
                 Rc::new("".to_string()),
-                self.construct_span(0),
+                self.span_from(self.cursor.offset(), 0),
             ));
-        }
-        let c = self.current().unwrap();
-        match c {
+        };
+        match b {
             // whitespace
-            ' ' | '\t' | '\r' => {
-                self.index += 1;
-                self.column += 1;
+            b' ' | b'\t' | b'\r' => {
+                self.cursor.bump();
                 self.next_token()
             }
             // linefeed
-            '\n' => {
+            b'\n' => {
+                let start = self.cursor.offset();
                 // skip newline character
-                self.index += 1;
-                self.line += 1;
-                self.column = 1;
+                self.cursor.bump();
                 // calculate indentation
                 let mut indent: usize = 0;
                 let mut continuation = false;
                 loop {
-                    match self.current() {
-                        Some(' ') => {
+                    match self.current_byte() {
+                        Some(b' ') => {
                             indent += 1;
-                            self.index += 1;
-                            self.column += 1;
+                            self.cursor.bump();
                         }
-                        Some('\t') => {
-                            indent += 4;
-                            self.index += 1;
-                            self.column += 1;
+                        Some(b'\t') => {
+                            indent += self.tab_width;
+                            self.cursor.bump();
                         }
-                        Some('\r') => {
-                            self.index += 1;
-                            self.column += 1;
+                        Some(b'\r') => {
+                            self.cursor.bump();
                         }
-                        Some('\\') => {
+                        Some(b'\\') => {
                             continuation = true;
-                            self.index += 1;
-                            self.column += 1;
+                            self.cursor.bump();
                         }
                         _ => break,
                     }
                 }
                 // compare indentation
+                let line_span = self.span_from(start, self.cursor.offset() - start);
                 let indent_stack_clone = self.indent_stack.clone();
-                let (prev_indent, prev_continuation) = indent_stack_clone.last().unwrap();
+                let (prev_indent, prev_continuation, prev_span) = indent_stack_clone.last().unwrap();
                 if indent > *prev_indent {
-                    self.indent_stack.push((indent, continuation));
+                    self.indent_stack.push((indent, continuation, line_span.clone()));
                     Ok(Token::new(
                         TokenKind::Indent,
                         Rc::new("".to_string()),
-                        self.construct_span(1),
+                        line_span,
                     ))
                 } else if indent < *prev_indent {
                     self.indent_stack.pop();
-                    if let Some((prev_indent, _)) = self.indent_stack.last() {
+                    if let Some((prev_indent, _, enclosing_span)) = self.indent_stack.last() {
                         if indent < *prev_indent {
-                            return Err(
-                                self.error("inconsistent indentation", self.construct_span(1))
-                            );
+                            return Err(self.error_frame(
+                                DiagnosticFrame::error("inconsistent indentation")
+                                    .with_label(line_span, "this line's indentation doesn't match any enclosing block")
+                                    .with_label(enclosing_span.clone(), "the enclosing block was indented here")
+                                    .with_hint("indent to match one of the enclosing blocks exactly"),
+                            ));
                         }
                     }
                     Ok(Token::new(
                         TokenKind::Dedent,
                         Rc::new("".to_string()),
-                        self.construct_span(1),
+                        line_span,
                     ))
                 } else {
                     if continuation && !*prev_continuation {
-                        return Err(self.error("inconsistent continuation", self.construct_span(1)));
+                        return Err(self.error_frame(
+                            DiagnosticFrame::error("inconsistent continuation")
+                                .with_label(line_span.clone(), "this line continues with `\\`")
+                                .with_label(prev_span.clone(), "but the enclosing block doesn't")
+                                .with_hint("either continue every line in the block or none of them"),
+                        ));
                     }
                     Ok(Token::new(
                         TokenKind::Linefeed,
                         Rc::new("".to_string()),
-                        self.construct_span(1),
+                        line_span,
                     ))
                 }
             }
-            'a'..='z' | 'A'..='Z' | '_' => {
-                self.index += 1;
-                self.column += 1;
-                let mut value = c.to_string();
-                loop {
-                    match self.current() {
-                        Some('a'..='z') | Some('A'..='Z') | Some('0'..='9') | Some('_') => {
-                            value.push(self.current().unwrap());
-                            self.index += 1;
-                            self.column += 1;
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
+                let start = self.cursor.offset();
+                self.cursor.bump();
+                Ok(self.identifier(start))
+            }
+            b'0'..=b'9' => {
+                let start = self.cursor.offset();
+                // `0x`/`0X`, `0b`/`0B`, `0o`/`0O` switch the literal to a non-decimal base,
+                // where only the prefix's own digits (plus `_` separators) are legal.
+                if b == b'0' {
+                    let base = match self.cursor.peek_second() {
+                        Some(b'x') | Some(b'X') => Some((16, "hexadecimal digits are 0-9 and a-f")),
+                        Some(b'b') | Some(b'B') => Some((2, "binary digits are 0 and 1")),
+                        Some(b'o') | Some(b'O') => Some((8, "octal digits are 0-7")),
+                        _ => None,
+                    };
+                    if let Some((radix, hint)) = base {
+                        self.cursor.bump_n(2);
+                        loop {
+                            match self.current_byte() {
+                                Some(b'_') => {
+                                    self.cursor.bump();
+                                }
+                                Some(d) if (d as char).is_digit(radix) => {
+                                    self.cursor.bump();
+                                }
+                                Some(d) if (d as char).is_alphanumeric() => {
+                                    return Err(self.error_frame(
+                                        DiagnosticFrame::error("invalid number representation")
+                                            .with_label(
+                                                self.span_from(self.cursor.offset(), 1),
+                                                format!("'{}' is not a valid base-{} digit", d as char, radix),
+                                            )
+                                            .with_hint(hint),
+                                    ));
+                                }
+                                _ => break,
+                            }
                         }
-                        _ => break,
+                        let value = self.cursor.contents[start..self.cursor.offset()].to_string();
+                        return Ok(Token::new(
+                            TokenKind::Integer,
+                            Rc::new(value),
+                            self.span_from(start, self.cursor.offset() - start),
+                        ));
                     }
                 }
-                let kind = match value.as_str() {
-                    "as" => TokenKind::As,
-                    "const" => TokenKind::Const,
-                    "fn" => TokenKind::Fn,
-                    "for" => TokenKind::For,
-                    "in" => TokenKind::In,
-                    "import" => TokenKind::Import,
-                    "mut" => TokenKind::Mut,
-                    "return" => TokenKind::Return,
-                    "struct" => TokenKind::Struct,
-                    "var" => TokenKind::Var,
-                    "float" => TokenKind::Float,
-                    "int" => TokenKind::Int,
-                    _ => TokenKind::Identifier,
-                };
-                Ok(Token::new(
-                    kind,
-                    Rc::new(value.clone()),
-                    self.construct_span(value.len()),
-                ))
-            }
-            '0'..='9' => {
-                let mut value = c.to_string();
-                self.index += 1;
-                self.column += 1;
+                self.cursor.bump();
                 loop {
-                    match self.current() {
-                        Some('0'..='9') => {
-                            value.push(self.current().unwrap());
-                            self.index += 1;
-                            self.column += 1;
+                    match self.current_byte() {
+                        Some(b'0'..=b'9') | Some(b'_') => {
+                            self.cursor.bump();
                         }
-                        Some('.') => {
-                            value.push(self.current().unwrap());
-                            self.index += 1;
-                            self.column += 1;
-                            loop {
-                                match self.current() {
-                                    Some('0'..='9') => {
-                                        value.push(self.current().unwrap());
-                                        self.index += 1;
-                                        self.column += 1;
-                                    }
-                                    _ => break,
-                                }
+                        // A second `.` means this is `a..b`, not a decimal point (`1.` followed
+                        // by `.b` would otherwise be swallowed into the float literal).
+                        Some(b'.') if self.cursor.peek_second() != Some(b'.') => {
+                            self.cursor.bump();
+                            while let Some(b'0'..=b'9') | Some(b'_') = self.current_byte() {
+                                self.cursor.bump();
                             }
                             break;
                         }
-                        Some('e') | Some('E') => {
-                            value.push(self.current().unwrap());
-                            self.index += 1;
-                            self.column += 1;
-                            match self.current() {
-                                Some('+') | Some('-') => {
-                                    value.push(self.current().unwrap());
-                                    self.index += 1;
-                                    self.column += 1;
+                        Some(b'e') | Some(b'E') => {
+                            self.cursor.bump();
+                            match self.current_byte() {
+                                Some(b'+') | Some(b'-') => {
+                                    self.cursor.bump();
                                 }
                                 _ => {}
                             }
-                            loop {
-                                match self.current() {
-                                    Some('0'..='9') => {
-                                        value.push(self.current().unwrap());
-                                        self.index += 1;
-                                        self.column += 1;
-                                    }
-                                    _ => break,
-                                }
+                            while let Some(b'0'..=b'9') | Some(b'_') = self.current_byte() {
+                                self.cursor.bump();
                             }
                             break;
                         }
                         _ => break,
                     }
                 }
+                let value = self.cursor.contents[start..self.cursor.offset()].to_string();
                 let kind = if value.contains('.') || value.contains('e') || value.contains('E') {
                     TokenKind::Floating
                 } else {
@@ -340,140 +614,238 @@ impl Tokenizer {
                 };
                 Ok(Token::new(
                     kind,
-                    Rc::new(value.clone()),
-                    self.construct_span(value.len()),
+                    Rc::new(value),
+                    self.span_from(start, self.cursor.offset() - start),
                 ))
             }
-            '"' => {
-                self.index += 1;
-                self.column += 1;
+            b'"' => {
+                let start = self.cursor.offset();
+                self.cursor.bump();
                 let mut value = String::new();
                 loop {
-                    match self.current() {
-                        Some('"') => {
-                            self.index += 1;
-                            self.column += 1;
+                    match self.current_byte() {
+                        Some(b'"') => {
+                            self.cursor.bump();
                             break;
                         }
-                        Some('\\') => {
-                            self.index += 1;
-                            self.column += 1;
-                            match self.current() {
-                                Some('n') => {
-                                    value.push('\n');
-                                    self.index += 1;
-                                    self.column += 1;
-                                }
-                                Some('r') => {
-                                    value.push('\r');
-                                    self.index += 1;
-                                    self.column += 1;
-                                }
-                                Some('t') => {
-                                    value.push('\t');
-                                    self.index += 1;
-                                    self.column += 1;
-                                }
-                                Some('\\') => {
-                                    value.push('\\');
-                                    self.index += 1;
-                                    self.column += 1;
-                                }
-                                Some('"') => {
-                                    value.push('"');
-                                    self.index += 1;
-                                    self.column += 1;
-                                }
-                                _ => {
-                                    return Err(self
-                                        .error("illegal escape sequence", self.construct_span(1)))
-                                }
-                            }
+                        Some(b'\\') => {
+                            self.cursor.bump();
+                            value.push(self.escape_sequence()?);
                         }
-                        Some(c) => {
+                        None => return Err(self.unterminated_literal_error("string", start)),
+                        // Non-ASCII bytes are never mistaken for `"` or `\` (those only
+                        // ever appear as single ASCII bytes in valid UTF-8), so decoding
+                        // one char here can't split a multibyte character in half.
+                        Some(_) => {
+                            let (c, len) = self.current_char().unwrap();
                             value.push(c);
-                            self.index += 1;
-                            self.column += 1;
-                        }
-                        None => {
-                            return Err(self.error("unexpected end of file", self.construct_span(1)))
+                            self.cursor.bump_n(len);
                         }
                     }
                 }
                 Ok(Token::new(
                     TokenKind::String,
-                    Rc::new(value.clone()),
-                    self.construct_span(value.len() + 2),
+                    Rc::new(value),
+                    self.span_from(start, self.cursor.offset() - start),
+                ))
+            }
+            b'\'' => {
+                let start = self.cursor.offset();
+                self.cursor.bump();
+                let mut value = String::new();
+                loop {
+                    match self.current_byte() {
+                        Some(b'\'') => {
+                            self.cursor.bump();
+                            break;
+                        }
+                        Some(b'\\') => {
+                            self.cursor.bump();
+                            value.push(self.escape_sequence()?);
+                        }
+                        Some(b'\n') | None => {
+                            return Err(self.unterminated_literal_error("character", start))
+                        }
+                        Some(_) => {
+                            let (c, len) = self.current_char().unwrap();
+                            value.push(c);
+                            self.cursor.bump_n(len);
+                        }
+                    }
+                }
+                let mut chars = value.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(_), None) => {}
+                    _ => {
+                        return Err(self.error_frame(
+                            DiagnosticFrame::error("character literal must contain exactly one character")
+                                .with_label(
+                                    self.span_from(start, self.cursor.offset() - start),
+                                    format!("this decodes to {} characters", value.chars().count()),
+                                ),
+                        ))
+                    }
+                }
+                Ok(Token::new(
+                    TokenKind::Character,
+                    Rc::new(value),
+                    self.span_from(start, self.cursor.offset() - start),
                 ))
             }
             // punctuation
-            '(' => self.single_token(TokenKind::LeftParenthesis),
-            ')' => self.single_token(TokenKind::RightParenthesis),
-            '{' => self.single_token(TokenKind::LeftBrace),
-            '}' => self.single_token(TokenKind::RightBrace),
-            '[' => self.single_token(TokenKind::LeftBracket),
-            ']' => self.single_token(TokenKind::RightBracket),
-            ',' => self.single_token(TokenKind::Comma),
-            '.' => self.single_token(TokenKind::Dot),
-            ':' => self.single_token(TokenKind::Colon),
-            ';' => Err(self.error(
-                "semicolon isn't used as a statement terminator",
-                self.construct_span(1),
+            b'(' => self.single_token(TokenKind::LeftParenthesis),
+            b')' => self.single_token(TokenKind::RightParenthesis),
+            b'{' => self.single_token(TokenKind::LeftBrace),
+            b'}' => self.single_token(TokenKind::RightBrace),
+            b'[' => self.single_token(TokenKind::LeftBracket),
+            b']' => self.single_token(TokenKind::RightBracket),
+            b',' => self.single_token(TokenKind::Comma),
+            b'.' => {
+                if self.cursor.peek_second() == Some(b'.') {
+                    let start = self.cursor.offset();
+                    self.cursor.bump_n(2);
+                    if self.current_byte() == Some(b'=') {
+                        self.cursor.bump();
+                        Ok(Token::new(
+                            TokenKind::DotDotEquals,
+                            Rc::new("..=".to_string()),
+                            self.span_from(start, 3),
+                        ))
+                    } else {
+                        Ok(Token::new(
+                            TokenKind::DotDot,
+                            Rc::new("..".to_string()),
+                            self.span_from(start, 2),
+                        ))
+                    }
+                } else {
+                    self.single_token(TokenKind::Dot)
+                }
+            }
+            b':' => self.single_token(TokenKind::Colon),
+            b';' => Err(self.error_frame(
+                DiagnosticFrame::error("semicolon isn't used as a statement terminator")
+                    .with_label(self.span_from(self.cursor.offset(), 1), "remove this `;`")
+                    .with_hint("statements are separated by newlines, not semicolons"),
             )),
             // operators
-            '+' => self.double_token('+', TokenKind::Plus, '=', TokenKind::PlusEquals),
-            '-' => {
-                if self.contents.chars().nth(self.index + 1) == Some('>') {
-                    self.index += 1;
-                    self.column += 1;
-                    self.double_token('-', TokenKind::ThinArrow, '>', TokenKind::ThinArrow)
+            b'+' => self.double_token(b'+', TokenKind::Plus, b'=', TokenKind::PlusEquals),
+            b'-' => {
+                if self.cursor.peek_second() == Some(b'>') {
+                    self.cursor.bump();
+                    self.double_token(b'-', TokenKind::ThinArrow, b'>', TokenKind::ThinArrow)
                 } else {
-                    self.double_token('-', TokenKind::Minus, '=', TokenKind::MinusEquals)
+                    self.double_token(b'-', TokenKind::Minus, b'=', TokenKind::MinusEquals)
                 }
             }
-            '*' => self.double_token('*', TokenKind::Asterisk, '=', TokenKind::AsteriskEquals),
-            '/' => {
-                if self.contents.chars().nth(self.index + 1) == Some('/') {
-                    self.index += 1;
-                    self.column += 1;
+            b'*' => self.double_token(b'*', TokenKind::Asterisk, b'=', TokenKind::AsteriskEquals),
+            b'/' => {
+                if self.cursor.peek_second() == Some(b'/') {
+                    self.cursor.bump();
                     loop {
-                        match self.current() {
-                            Some('\n') => {
-                                self.index += 1;
-                                self.line += 1;
-                                self.column = 1;
+                        match self.current_byte() {
+                            Some(b'\n') => {
+                                self.cursor.bump();
                                 break;
                             }
                             Some(_) => {
-                                self.index += 1;
-                                self.column += 1;
+                                self.cursor.bump();
                             }
                             None => break,
                         }
                     }
                     self.next_token()
+                } else if self.cursor.peek_second() == Some(b'*') {
+                    let start = self.cursor.offset();
+                    self.cursor.bump_n(2);
+                    let mut depth = 1usize;
+                    loop {
+                        match self.current_byte() {
+                            Some(b'*') if self.cursor.peek_second() == Some(b'/') => {
+                                self.cursor.bump_n(2);
+                                depth -= 1;
+                                if depth == 0 {
+                                    break;
+                                }
+                            }
+                            Some(b'/') if self.cursor.peek_second() == Some(b'*') => {
+                                self.cursor.bump_n(2);
+                                depth += 1;
+                            }
+                            Some(_) => {
+                                self.cursor.bump();
+                            }
+                            None => {
+                                return Err(self.error_frame(
+                                    DiagnosticFrame::error("unterminated block comment")
+                                        .with_label(self.span_from(start, 2), "block comment starts here")
+                                        .with_hint("block comments nest, so every `/*` needs its own `*/`"),
+                                ));
+                            }
+                        }
+                    }
+                    self.next_token()
                 } else {
-                    self.double_token('/', TokenKind::Slash, '=', TokenKind::SlashEquals)
+                    self.double_token(b'/', TokenKind::Slash, b'=', TokenKind::SlashEquals)
                 }
             }
-            '%' => self.double_token('%', TokenKind::Percent, '=', TokenKind::PercentEquals),
-            '=' => self.double_token('=', TokenKind::Equals, '=', TokenKind::EqualsEquals),
-            '!' => self.double_token('!', TokenKind::Bang, '=', TokenKind::BangEquals),
-            '<' => self.double_token('<', TokenKind::LessThan, '=', TokenKind::LessThanEquals),
-            '>' => self.double_token(
-                '>',
-                TokenKind::GreaterThan,
-                '=',
-                TokenKind::GreaterThanEquals,
-            ),
-            '&' => self.double_token('&', TokenKind::BitwiseAnd, '&', TokenKind::And),
-            '|' => self.double_token('|', TokenKind::BitwiseOr, '|', TokenKind::Or),
-            '^' => self.single_token(TokenKind::BitwiseXor),
-            '~' => self.single_token(TokenKind::BitwiseNot),
-            _ => Err(self.error(
-                format!("illegal character '{}'", c).as_str(),
-                self.construct_span(1),
-            )),
+            b'%' => self.double_token(b'%', TokenKind::Percent, b'=', TokenKind::PercentEquals),
+            b'=' => self.double_token(b'=', TokenKind::Equals, b'=', TokenKind::EqualsEquals),
+            b'!' => self.double_token(b'!', TokenKind::Bang, b'=', TokenKind::BangEquals),
+            b'<' => {
+                if self.cursor.peek_second() == Some(b'<') {
+                    let start = self.cursor.offset();
+                    self.cursor.bump_n(2);
+                    Ok(Token::new(
+                        TokenKind::ShiftLeft,
+                        Rc::new("<<".to_string()),
+                        self.span_from(start, 2),
+                    ))
+                } else {
+                    self.double_token(b'<', TokenKind::LessThan, b'=', TokenKind::LessThanEquals)
+                }
+            }
+            b'>' => {
+                if self.cursor.peek_second() == Some(b'>') {
+                    let start = self.cursor.offset();
+                    self.cursor.bump_n(2);
+                    Ok(Token::new(
+                        TokenKind::ShiftRight,
+                        Rc::new(">>".to_string()),
+                        self.span_from(start, 2),
+                    ))
+                } else {
+                    self.double_token(
+                        b'>',
+                        TokenKind::GreaterThan,
+                        b'=',
+                        TokenKind::GreaterThanEquals,
+                    )
+                }
+            }
+            b'&' => self.double_token(b'&', TokenKind::BitwiseAnd, b'&', TokenKind::And),
+            b'|' => self.double_token(b'|', TokenKind::BitwiseOr, b'|', TokenKind::Or),
+            b'^' => self.single_token(TokenKind::BitwiseXor),
+            b'~' => self.single_token(TokenKind::BitwiseNot),
+            _ => {
+                // Not a recognized ASCII delimiter; decode the full codepoint. A
+                // non-ASCII `XID_Start` character (e.g. `λ`) begins a Unicode
+                // identifier; anything else is reported as illegal, advancing past
+                // it as one unit, not one byte, keeping the cursor on a char
+                // boundary for later slicing.
+                let (c, len) = self.current_char().unwrap();
+                if c.is_alphabetic() {
+                    let start = self.cursor.offset();
+                    self.cursor.bump_n(len);
+                    return Ok(self.identifier(start));
+                }
+                let span = self.span_from(self.cursor.offset(), len);
+                self.cursor.bump_n(len);
+                Err(self.error_frame(
+                    DiagnosticFrame::error(format!("illegal character '{}'", c))
+                        .with_label(span, "not a valid token start"),
+                ))
+            }
         }
     }
 }
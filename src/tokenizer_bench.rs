@@ -0,0 +1,78 @@
+//! Not wired into `cargo bench` (this tree has no `Cargo.toml`/harness target), so
+//! this is a `#[test]`/`#[ignore]`d timing comparison instead: run with
+//! `cargo test --release tokenizer_bench -- --ignored --nocapture` to see the gap
+//! between the old `chars().nth(index)` scan this module replaced and the current
+//! byte-cursor one in `Tokenizer::tokenize`.
+#![cfg(test)]
+
+use std::rc::Rc;
+use std::time::Instant;
+
+use crate::span::SourceMap;
+use crate::tokenizer::Tokenizer;
+
+/// Reproduces the identifier/whitespace/number scan `Tokenizer` used before the
+/// byte-cursor rewrite: `self.contents.chars().nth(self.index)` re-walks the string
+/// from the start on every single-character lookup, making the whole scan O(n^2).
+fn tokenize_char_by_char(source: &str) -> usize {
+    let mut index = 0;
+    let mut count = 0;
+    let current = |index: usize| source.chars().nth(index);
+    while let Some(c) = current(index) {
+        match c {
+            'a'..='z' | 'A'..='Z' | '_' => {
+                index += 1;
+                while matches!(current(index), Some('a'..='z') | Some('A'..='Z') | Some('0'..='9') | Some('_')) {
+                    index += 1;
+                }
+            }
+            '0'..='9' => {
+                index += 1;
+                while matches!(current(index), Some('0'..='9')) {
+                    index += 1;
+                }
+            }
+            _ => index += 1,
+        }
+        count += 1;
+    }
+    count
+}
+
+fn repeated_source(repeats: usize) -> String {
+    "fn add(a: int, b: int) -> int:\n    return a + b\n".repeat(repeats)
+}
+
+#[test]
+#[ignore]
+fn byte_cursor_is_faster_than_char_by_char_on_large_input() {
+    let source = repeated_source(2_000);
+
+    let start = Instant::now();
+    let char_count = tokenize_char_by_char(&source);
+    let char_elapsed = start.elapsed();
+
+    let source_map = SourceMap::shared();
+    let file = source_map
+        .borrow_mut()
+        .add_file("<bench>".to_string(), source.clone());
+    let start = Instant::now();
+    let tokens = Tokenizer::new(file, Rc::new(source), source_map)
+        .tokenize()
+        .expect("bench fixture must tokenize cleanly");
+    let byte_elapsed = start.elapsed();
+
+    println!(
+        "char-by-char: {:?} ({} tokens) | byte-cursor: {:?} ({} tokens)",
+        char_elapsed,
+        char_count,
+        byte_elapsed,
+        tokens.len()
+    );
+    assert!(
+        byte_elapsed < char_elapsed,
+        "byte-cursor scan ({:?}) should beat the O(n^2) char-by-char scan ({:?})",
+        byte_elapsed,
+        char_elapsed
+    );
+}